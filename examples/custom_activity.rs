@@ -40,7 +40,7 @@ async fn main() -> Result<()> {
                         )
                         .state("hiii")
                         .party(Party::builder().size([12, 24]).build())
-                        .call(),
+                        .call()?,
                 )
                 .build(),
         )
@@ -60,7 +60,7 @@ async fn main() -> Result<()> {
         subscribe_to_channel(&client, channel_id).await?;
     }
     loop {
-        let data = client.read_event_queue().await;
+        let data = client.read_event_queue().await?;
         match data {
             sdkcord::payload::EventData::MessageCreate(message_create_data) => {
                 info!("MESSAGE CREATED: {:?}", message_create_data);
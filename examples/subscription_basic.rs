@@ -35,8 +35,7 @@ async fn main() -> Result<()> {
     .await?;
     let client_listener = client.clone();
     let joiner = tokio::spawn(async move {
-        loop {
-            let evt = client_listener.read_event_queue().await;
+        while let Ok(evt) = client_listener.read_event_queue().await {
             tracing::info!("Received event: {:?}", evt);
         }
     });
@@ -15,7 +15,12 @@
 //! server sends it to the reader actor, which then sends it to the coordinator actor for
 //! processing.
 
-use std::{io, marker::PhantomData, sync::Arc, time::Duration};
+use std::{
+    io,
+    marker::PhantomData,
+    sync::{Arc, OnceLock},
+    time::Duration,
+};
 
 use dashmap::DashMap;
 use futures::SinkExt;
@@ -28,7 +33,7 @@ use kameo::{
 use thiserror::Error;
 use tokio::{
     io::{AsyncRead, AsyncWrite},
-    sync::oneshot,
+    sync::{broadcast, oneshot},
 };
 use tokio_util::codec::{FramedRead, FramedWrite};
 use tracing::{error, instrument, trace};
@@ -37,7 +42,7 @@ use uuid::Uuid;
 use crate::{
     SerdeProcessingError,
     codec::{Frame, FrameCodec},
-    payload::{Event, EventData, PayloadResponse, Request},
+    payload::{CloseData, Event, EventData, PayloadResponse, Request, common::opcode::Opcode},
     pool::{Client, SerdePoolError},
 };
 
@@ -52,6 +57,10 @@ pub(crate) struct Coordinator<W> {
     /// Pending client requests where we map the nonce to the caller
     pending_requests: Arc<DashMap<Uuid, oneshot::Sender<PayloadResponse>>>,
     evt_queue_tx: async_channel::Sender<EventData>,
+    /// Copies of every event for the receivers of [crate::client::SdkClient::event_stream]
+    evt_broadcast_tx: broadcast::Sender<EventData>,
+    /// Data of the CLOSE frame once Discord has closed the connection
+    closed: Arc<OnceLock<CloseData>>,
 }
 
 impl<T> Actor for Coordinator<ActorRef<Writer<T>>>
@@ -76,11 +85,15 @@ where
     pub(crate) fn new(
         writer: ActorRef<Writer<T>>,
         evt_queue_tx: async_channel::Sender<EventData>,
+        evt_broadcast_tx: broadcast::Sender<EventData>,
+        closed: Arc<OnceLock<CloseData>>,
     ) -> Self {
         Self {
             writer,
             pending_requests: Arc::new(DashMap::new()),
             evt_queue_tx,
+            evt_broadcast_tx,
+            closed,
         }
     }
 }
@@ -103,12 +116,13 @@ where
     ) -> Self::Reply {
         let request = msg.0;
         let callback = msg.1;
-        let nonce = {
-            if let Request::Payload(payload) = &request {
-                payload.0.nonce.unwrap()
-            } else {
-                CONNECT_UUID
-            }
+        let nonce = match &request {
+            Request::Payload(payload) => match payload.0.nonce {
+                Some(nonce) => nonce,
+                // the response could never be matched to the request
+                None => return Err(CoordinatorError::MissingNonce(request)),
+            },
+            Request::Connect(_) => CONNECT_UUID,
         };
 
         self.pending_requests.insert(nonce, callback);
@@ -162,18 +176,49 @@ where
             }
             Some(_evt) => {
                 let evt_queue_tx = self.evt_queue_tx.clone();
+                let evt_broadcast_tx = self.evt_broadcast_tx.clone();
                 tokio::spawn(async move {
-                    let evt_data = EventData::from(msg.0.data.unwrap());
-                    evt_queue_tx
-                        .send(evt_data)
-                        .await
-                        .expect("channel closed...");
+                    match EventData::try_from(msg) {
+                        Ok(evt_data) => {
+                            // no receivers is not an error; the queue still gets the event
+                            let _ = evt_broadcast_tx.send(evt_data.clone());
+                            if let Err(err) = evt_queue_tx.send(evt_data).await {
+                                error!("event queue is closed; dropping event: {:?}", err.0);
+                            }
+                        }
+                        Err(err) => error!("failed to convert event: {:?}", err),
+                    }
                 });
             }
         }
     }
 }
 
+/// Message sent to the [Coordinator] when Discord closes the IPC connection
+#[derive(Debug)]
+pub(crate) struct ConnectionClosed(pub(crate) CloseData);
+
+impl<T> Message<ConnectionClosed> for Coordinator<ActorRef<Writer<T>>>
+where
+    T: Send + Sync + 'static,
+    T: AsyncWrite + Unpin,
+{
+    type Reply = ();
+
+    #[instrument(level = "trace", skip(self))]
+    async fn handle(
+        &mut self,
+        msg: ConnectionClosed,
+        _: &mut Context<Self, Self::Reply>,
+    ) -> Self::Reply {
+        error!("discord closed the ipc connection: {:?}", msg.0);
+        let _ = self.closed.set(msg.0);
+        // dropping the senders wakes up every pending request
+        self.pending_requests.clear();
+        self.evt_queue_tx.close();
+    }
+}
+
 /// Reader actor for handling messages from the IPC server
 pub(crate) struct Reader<T, W: Actor> {
     /// Client for deserializing the frame from IPC server
@@ -357,6 +402,19 @@ async fn process_stream_message_frame<W>(
     W: Send + Sync + 'static,
     W: AsyncWrite + Unpin,
 {
+    if frame.opcode == Opcode::Close {
+        let data = serde_json::from_slice::<CloseData>(&frame.payload).unwrap_or_else(|err| {
+            error!("failed to deserialize close frame: {}", err);
+            CloseData {
+                code: None,
+                message: None,
+            }
+        });
+        if let Err(err) = coordinator.tell(ConnectionClosed(data)).await {
+            error!("failed to send close to coordinator: {}", err);
+        }
+        return;
+    }
     match deserializer_client.deserialize(frame).await {
         Ok(Ok(resp)) => {
             if let Err(err) = coordinator.tell(resp).await {
@@ -377,6 +435,9 @@ pub(crate) enum CoordinatorError {
     /// The reques timed out
     #[error("timeout writing to ipc server")]
     WriterTimeout(Option<Request>),
+    /// The request has no nonce to match its response to
+    #[error("request does not have a nonce")]
+    MissingNonce(Request),
 }
 
 /// Error types for what happens within the [`Writer`] actor
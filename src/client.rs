@@ -11,7 +11,10 @@
 //!  - `RpcNotificationsRead`
 //!
 //! to get access to all of the commands in the SDK.
-use std::{sync::Arc, time::Duration};
+use std::{
+    sync::{Arc, OnceLock},
+    time::Duration,
+};
 
 use kameo::{actor::ActorRef, actor::Spawn, error::SendError};
 use thiserror::Error;
@@ -22,8 +25,12 @@ use crate::{
     actors::{Coordinator, Reader, ReaderArgs, Writer},
     codec::FrameCodec,
     config::{Config, OAuth2Config},
+    link::DiscordLink,
     oauth2::{OAuth2Error, TokenManager},
-    payload::*,
+    payload::{
+        common::{channel::ChannelType, soundboard::SoundboardSound},
+        *,
+    },
     pool::{deserialize, serialize, spawn_pool},
 };
 
@@ -35,7 +42,10 @@ use {crate::conn::windows::ClientWriteHalf as WriteHalf, crate::conn::windows::c
 
 use tokio::{
     io::{AsyncRead, AsyncWrite},
-    sync::oneshot::{self},
+    sync::{
+        RwLock, broadcast,
+        oneshot::{self, error::RecvError},
+    },
     time::Instant,
 };
 
@@ -44,6 +54,7 @@ use tokio::{
 pub struct SdkClient {
     inner: Arc<InnerSdkClient>,
     token_manager: Option<Arc<TokenManager>>,
+    soundboard_sounds: Arc<RwLock<Option<GetSoundboardSoundsData>>>,
 }
 
 impl SdkClient {
@@ -83,13 +94,42 @@ impl SdkClient {
         Ok(SdkClient {
             inner,
             token_manager,
+            soundboard_sounds: Arc::new(RwLock::new(None)),
+        })
+    }
+
+    /// Constructs a client without OAuth2 on top of an already connected IPC socket
+    #[cfg(all(test, unix))]
+    pub(crate) async fn with_stream(
+        config: Config,
+        client_id: &str,
+        stream: tokio::net::UnixStream,
+    ) -> Result<Self, SdkClientError> {
+        let (rh, wh) = stream.into_split();
+        let inner = InnerSdkClient::handshake(rh, wh, config, client_id).await?;
+        Ok(SdkClient {
+            inner: Arc::new(inner),
+            token_manager: None,
+            soundboard_sounds: Arc::new(RwLock::new(None)),
         })
     }
 
-    pub async fn read_event_queue(&self) -> EventData {
+    /// Read the next event from the queue of subscribed events
+    ///
+    /// # Errors
+    /// A [SdkClientError] is returned if the event queue has been closed
+    pub async fn read_event_queue(&self) -> Result<EventData, SdkClientError> {
         self.inner.get_event_data().await
     }
 
+    /// Returns a receiver of every event of the subscriptions
+    ///
+    /// Unlike [SdkClient::read_event_queue], every receiver gets its own copy of each event sent
+    /// after it was created, so events can be observed without taking them from the queue.
+    pub fn event_stream(&self) -> broadcast::Receiver<EventData> {
+        self.inner.evt_broadcast_tx.subscribe()
+    }
+
     impl_request! {
         /// Send a get guild request to the IPC server
         get_guild; GetGuild
@@ -161,6 +201,332 @@ impl SdkClient {
         get_channels;
         GetChannels
     }
+
+    impl_request! {
+        /// Send a set certified devices request to the IPC server.
+        set_certified_devices;
+        SetCertifiedDevices
+    }
+
+    impl_request! {
+        /// Send a send activity join invite request to the IPC server.
+        send_activity_join_invite;
+        SendActivityJoinInvite
+    }
+
+    impl_request! {
+        /// Send a close activity request to the IPC server.
+        close_activity_request;
+        CloseActivityRequest
+    }
+
+    impl_request! {
+        /// Send a capture shortcut request to the IPC server.
+        ///
+        /// The keys pressed by the user are sent through the `CAPTURE_SHORTCUT_CHANGE` event
+        /// until capturing is stopped.
+        capture_shortcut;
+        CaptureShortcut
+    }
+
+    impl_request! {
+        /// Send a get relationships request to the IPC server.
+        get_relationships;
+        GetRelationships
+    }
+
+    impl_request! {
+        /// Send a toggle video request to the IPC server.
+        toggle_video;
+        ToggleVideo
+    }
+
+    impl_request! {
+        /// Send a toggle screenshare request to the IPC server.
+        toggle_screenshare;
+        ToggleScreenshare
+    }
+
+    impl_request! {
+        /// Send a set config request to the IPC server.
+        set_config;
+        SetConfig
+    }
+
+    impl_request! {
+        /// Send a get soundboard sounds request to the IPC server without caching the sounds.
+        fn request_soundboard_sounds;
+        GetSoundboardSounds
+    }
+
+    /// Send a get soundboard sounds request to the IPC server
+    ///
+    /// The returned sounds replace the ones cached by a previous call and can be read back
+    /// through [SdkClient::cached_soundboard_sounds].
+    ///
+    /// # Errors
+    /// A [SdkClientError] is returned if the client fails to send the request or if the server
+    /// responds with an error
+    pub async fn get_soundboard_sounds(
+        &self,
+        args: GetSoundboardSoundsArgs,
+    ) -> SdkClientResult<GetSoundboardSoundsData> {
+        let data = self.request_soundboard_sounds(args).await?;
+        *self.soundboard_sounds.write().await = Some(data.as_ref().clone());
+        Ok(data)
+    }
+
+    /// Returns the soundboard sounds cached by the last [SdkClient::get_soundboard_sounds] call
+    ///
+    /// Only the sounds of the given guild are returned if `guild_id` is set. [None] is returned
+    /// if the sounds have not been fetched yet.
+    pub async fn cached_soundboard_sounds(
+        &self,
+        guild_id: Option<&str>,
+    ) -> Option<Vec<SoundboardSound>> {
+        let sounds = self.soundboard_sounds.read().await;
+        let sounds = sounds.as_ref()?;
+        match guild_id {
+            Some(guild_id) => Some(sounds.for_guild(guild_id).cloned().collect()),
+            None => Some(sounds.0.clone()),
+        }
+    }
+
+    impl_request! {
+        /// Send a play soundboard sound request to the IPC server.
+        play_soundboard_sound;
+        PlaySoundboardSound
+    }
+
+    impl_request! {
+        /// Send a set overlay locked request to the IPC server.
+        set_overlay_locked;
+        SetOverlayLocked
+    }
+
+    impl_request! {
+        /// Send a open overlay activity invite request to the IPC server.
+        open_overlay_activity_invite;
+        OpenOverlayActivityInvite
+    }
+
+    impl_request! {
+        /// Send a open overlay guild invite request to the IPC server.
+        open_overlay_guild_invite;
+        OpenOverlayGuildInvite
+    }
+
+    impl_request! {
+        /// Send a open overlay voice settings request to the IPC server.
+        open_overlay_voice_settings;
+        OpenOverlayVoiceSettings
+    }
+
+    impl_request! {
+        /// Send a invite browser request to the IPC server.
+        invite_browser;
+        InviteBrowser
+    }
+
+    impl_request! {
+        /// Send a guild template browser request to the IPC server.
+        guild_template_browser;
+        GuildTemplateBrowser
+    }
+
+    impl_request! {
+        /// Send a deep link request to the IPC server.
+        deep_link;
+        DeepLink
+    }
+
+    impl_request! {
+        /// Send a get entitlements request to the IPC server.
+        get_entitlements;
+        GetEntitlements
+    }
+
+    impl_request! {
+        /// Send a get skus request to the IPC server.
+        get_skus;
+        GetSkus
+    }
+
+    impl_request! {
+        /// Send a start purchase request to the IPC server.
+        start_purchase;
+        StartPurchase
+    }
+
+    impl_request! {
+        /// Send a create lobby request to the IPC server.
+        create_lobby;
+        CreateLobby
+    }
+
+    impl_request! {
+        /// Send a update lobby request to the IPC server.
+        update_lobby;
+        UpdateLobby
+    }
+
+    impl_request! {
+        /// Send a delete lobby request to the IPC server.
+        delete_lobby;
+        DeleteLobby
+    }
+
+    impl_request! {
+        /// Send a connect to lobby request to the IPC server.
+        connect_to_lobby;
+        ConnectToLobby
+    }
+
+    impl_request! {
+        /// Send a disconnect from lobby request to the IPC server.
+        disconnect_from_lobby;
+        DisconnectFromLobby
+    }
+
+    impl_request! {
+        /// Send a send to lobby request to the IPC server.
+        send_to_lobby;
+        SendToLobby
+    }
+
+    impl_request! {
+        /// Send a update lobby member request to the IPC server.
+        update_lobby_member;
+        UpdateLobbyMember
+    }
+
+    impl_request! {
+        /// Send a get user request to the IPC server.
+        get_user;
+        GetUser
+    }
+
+    impl_request! {
+        /// Send a get channel permissions request to the IPC server.
+        ///
+        /// The returned [Permissions][crate::payload::common::permission::Permissions] can be
+        /// checked before calling [SdkClient::select_voice_channel] or
+        /// [SdkClient::select_text_channel].
+        get_channel_permissions;
+        GetChannelPermissions
+    }
+
+    impl_request! {
+        /// Send a get activity instance connected participants request to the IPC server.
+        #[cfg(feature = "embedded-activity")]
+        get_activity_instance_connected_participants;
+        GetActivityInstanceConnectedParticipants
+    }
+
+    impl_request! {
+        /// Send a set orientation lock state request to the IPC server.
+        #[cfg(feature = "embedded-activity")]
+        set_orientation_lock_state;
+        SetOrientationLockState
+    }
+
+    impl_request! {
+        /// Send a get platform behaviors request to the IPC server.
+        #[cfg(feature = "embedded-activity")]
+        get_platform_behaviors;
+        GetPlatformBehaviors
+    }
+
+    impl_request! {
+        /// Send a user settings get locale request to the IPC server.
+        #[cfg(feature = "embedded-activity")]
+        user_settings_get_locale;
+        UserSettingsGetLocale
+    }
+
+    impl_request! {
+        /// Send a open share moment dialog request to the IPC server.
+        #[cfg(feature = "embedded-activity")]
+        open_share_moment_dialog;
+        OpenShareMomentDialog
+    }
+
+    impl_request! {
+        /// Send a initiate image upload request to the IPC server.
+        #[cfg(feature = "embedded-activity")]
+        initiate_image_upload;
+        InitiateImageUpload
+    }
+
+    /// Open a parsed [DiscordLink] inside of the Discord client
+    ///
+    /// Invites and guild templates are opened with [SdkClient::invite_browser] and
+    /// [SdkClient::guild_template_browser]. Links to a message are opened with
+    /// [SdkClient::deep_link], while links to a channel are resolved with
+    /// [SdkClient::get_channel] and then selected with [SdkClient::select_voice_channel] or
+    /// [SdkClient::select_text_channel] depending on the type of the channel.
+    ///
+    /// # Errors
+    /// A [SdkClientError] is returned if any of the requests fails
+    pub async fn open_link(&self, link: &DiscordLink) -> Result<(), SdkClientError> {
+        match link {
+            DiscordLink::Invite { code } => {
+                self.invite_browser(InviteBrowserArgs::builder().code(code).build())
+                    .await?;
+            }
+            DiscordLink::GuildTemplate { code } => {
+                self.guild_template_browser(GuildTemplateBrowserArgs::builder().code(code).build())
+                    .await?;
+            }
+            DiscordLink::Channel {
+                guild_id,
+                channel_id,
+                message_id: Some(message_id),
+            } => {
+                let params = DeepLinkParams::builder()
+                    .maybe_guild_id(guild_id.as_deref())
+                    .channel_id(channel_id)
+                    .message_id(message_id)
+                    .build();
+                self.deep_link(
+                    DeepLinkArgs::builder()
+                        .link_type(DeepLinkType::Channel)
+                        .params(params)
+                        .build(),
+                )
+                .await?;
+            }
+            DiscordLink::Channel {
+                channel_id,
+                message_id: None,
+                ..
+            } => {
+                let channel = self
+                    .get_channel(GetChannelArgs(channel_id.as_str().into()))
+                    .await?;
+                let channel_type = channel.0.channel.and_then(|channel| channel.channel_type);
+                if matches!(
+                    channel_type,
+                    Some(ChannelType::GuildVoice | ChannelType::GuildStageVoice)
+                ) {
+                    self.select_voice_channel(
+                        SelectVoiceChannelArgs::builder()
+                            .channel_id(channel_id)
+                            .build(),
+                    )
+                    .await?;
+                } else {
+                    self.select_text_channel(
+                        SelectTextChannelArgs::builder()
+                            .channel_id(channel_id)
+                            .build(),
+                    )
+                    .await?;
+                }
+            }
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug)]
@@ -168,6 +534,9 @@ pub(crate) struct InnerSdkClient {
     coordinator: ActorRef<Coordinator<ActorRef<Writer<WriteHalf>>>>,
     request_timeout: Duration,
     evt_queue_rx: async_channel::Receiver<EventData>,
+    evt_broadcast_tx: broadcast::Sender<EventData>,
+    /// Data of the CLOSE frame once Discord has closed the connection
+    closed: Arc<OnceLock<CloseData>>,
 }
 
 impl InnerSdkClient {
@@ -178,16 +547,10 @@ impl InnerSdkClient {
         let response = self
             .send_request(PayloadRequest::builder().request(args).build())
             .await?;
-        if let Some(Data::Authenticate(data)) = response.0.data {
-            Ok(data)
-        } else if let Some(Data::Error(error)) = response.0.data {
-            Err(SdkClientError::ResponseError { error })
-        } else {
-            error!(
-                "response should always have data but could not be found... panicking...; final response: {:?}",
-                response
-            );
-            panic!("some form of data should always be returned...");
+        match response.0.data {
+            Some(Data::Authenticate(data)) => Ok(data),
+            Some(Data::Error(error)) => Err(SdkClientError::ResponseError { error }),
+            _ => Err(SdkClientError::UnexpectedResponse(Box::new(response))),
         }
     }
 
@@ -195,16 +558,10 @@ impl InnerSdkClient {
         let response = self
             .send_request(PayloadRequest::builder().request(args).build())
             .await?;
-        if let Some(Data::Authorize(data)) = response.0.data {
-            Ok(data)
-        } else if let Some(Data::Error(error)) = response.0.data {
-            Err(SdkClientError::ResponseError { error })
-        } else {
-            error!(
-                "response should always have data but could not be found... panicking...; final response: {:?}",
-                response
-            );
-            panic!("some form of data should always be returned...");
+        match response.0.data {
+            Some(Data::Authorize(data)) => Ok(data),
+            Some(Data::Error(error)) => Err(SdkClientError::ResponseError { error }),
+            _ => Err(SdkClientError::UnexpectedResponse(Box::new(response))),
         }
     }
 
@@ -214,17 +571,41 @@ impl InnerSdkClient {
             {
                 connect_unix()
                     .await
-                    .map_err(|err| SdkClientError::ConnectionFailed(err.to_string()))?
+                    .map_err(|err| SdkClientError::ConnectionFailed(Box::new(err)))?
             }
             #[cfg(windows)]
             {
                 connect_windows()
                     .await
-                    .map_err(|err| SdkClientError::ConnectionFailed(err.to_string()))?
+                    .map_err(|err| SdkClientError::ConnectionFailed(Box::new(err)))?
             }
         };
+        Self::handshake(rh, wh, config, client_id).await
+    }
+
+    /// Spawns the actors on top of the connection and waits for the READY event
+    async fn handshake<R>(
+        rh: R,
+        wh: WriteHalf,
+        config: Config,
+        client_id: &str,
+    ) -> Result<InnerSdkClient, SdkClientError>
+    where
+        R: Send + Sync + 'static,
+        R: AsyncRead + Unpin,
+    {
         let (evt_queue_tx, evt_queue_rx) = async_channel::bounded::<EventData>(1024);
-        let coordinator = setup(wh, rh, &config, evt_queue_tx).await;
+        let (evt_broadcast_tx, _) = broadcast::channel::<EventData>(1024);
+        let closed = Arc::new(OnceLock::new());
+        let coordinator = setup(
+            wh,
+            rh,
+            &config,
+            evt_queue_tx,
+            evt_broadcast_tx.clone(),
+            closed.clone(),
+        )
+        .await;
         let (sndr, recv) = oneshot::channel::<PayloadResponse>();
         // Setup Initial IPC connection
         {
@@ -234,7 +615,7 @@ impl InnerSdkClient {
                     sndr,
                 ))
                 .await
-                .map_err(|err| SdkClientError::ConnectionFailed(err.to_string()))?;
+                .map_err(|err| SdkClientError::ConnectionFailed(Box::new(err)))?;
 
             tokio::time::timeout_at(
                 Instant::now() + Duration::from_secs(config.request_timeout),
@@ -242,7 +623,12 @@ impl InnerSdkClient {
             )
             .await
             .map_err(|_| SdkClientError::Timeout)?
-            .map_err(|err| SdkClientError::ConnectionFailed(err.to_string()))?;
+            .map_err(|err| {
+                closed.get().map_or_else(
+                    || SdkClientError::ConnectionFailed(Box::new(err)),
+                    |data| SdkClientError::ConnectionClosed(Box::new(data.clone())),
+                )
+            })?;
         }
 
         let request_timeout = Duration::from_secs(config.request_timeout);
@@ -251,15 +637,24 @@ impl InnerSdkClient {
             coordinator,
             request_timeout,
             evt_queue_rx,
+            evt_broadcast_tx,
+            closed,
         };
         Ok(sdk_client)
     }
 
-    async fn get_event_data(&self) -> EventData {
-        self.evt_queue_rx
-            .recv()
-            .await
-            .expect("failed since channel is closed")
+    async fn get_event_data(&self) -> Result<EventData, SdkClientError> {
+        self.evt_queue_rx.recv().await.map_err(|err| {
+            self.closed_error()
+                .unwrap_or(SdkClientError::EventQueueClosed(err))
+        })
+    }
+
+    /// Returns a [SdkClientError::ConnectionClosed] if Discord has closed the connection
+    fn closed_error(&self) -> Option<SdkClientError> {
+        self.closed
+            .get()
+            .map(|data| SdkClientError::ConnectionClosed(Box::new(data.clone())))
     }
 
     /// Send a request to the IPC server
@@ -275,43 +670,38 @@ impl InnerSdkClient {
         &self,
         request: PayloadRequest,
     ) -> Result<PayloadResponse, SdkClientError> {
+        if let Some(err) = self.closed_error() {
+            return Err(err);
+        }
         let (sndr, recv) = oneshot::channel::<PayloadResponse>();
         if let Err(send_err) = self
             .coordinator
             .tell((Request::Payload(request), sndr))
             .await
         {
-            match send_err {
-                SendError::ActorNotRunning(err) => {
-                    if let Request::Payload(payload) = err.0 {
-                        return Err(SdkClientError::SendRequest(Some(payload)));
-                    } else {
-                        return Err(SdkClientError::SendRequest(None));
-                    }
-                }
-                SendError::ActorStopped => SdkClientError::SendRequest(None),
-                SendError::MailboxFull(err) => {
-                    if let Request::Payload(payload) = err.0 {
-                        return Err(SdkClientError::SendRequest(Some(payload)));
-                    } else {
-                        return Err(SdkClientError::SendRequest(None));
-                    }
-                }
-                SendError::HandlerError(err) => {
-                    return Err(SdkClientError::InternalCoordinator(err.to_string()));
+            let payload = |request: Request| match request {
+                Request::Payload(payload) => Some(payload),
+                Request::Connect(_) => None,
+            };
+            return Err(match send_err {
+                SendError::ActorNotRunning((request, _))
+                | SendError::MailboxFull((request, _))
+                | SendError::Timeout(Some((request, _))) => {
+                    SdkClientError::SendRequest(payload(request))
                 }
-                SendError::Timeout(_) => {
-                    // server has a timeout on its end so client will always receive a response...
-                    panic!(
-                        "this should never happen since we don't have timeout setup from client to server!"
-                    )
+                SendError::ActorStopped | SendError::Timeout(None) => {
+                    SdkClientError::SendRequest(None)
                 }
-            };
+                SendError::HandlerError(err) => SdkClientError::InternalCoordinator(Box::new(err)),
+            });
         }
         let resp = tokio::time::timeout_at(Instant::now() + self.request_timeout, recv)
             .await
             .map_err(|_| SdkClientError::Timeout)?
-            .map_err(|err| SdkClientError::ResponseDropped(err.to_string()))?;
+            .map_err(|err| {
+                self.closed_error()
+                    .unwrap_or(SdkClientError::ResponseDropped(err))
+            })?;
         Ok(resp)
     }
 }
@@ -321,6 +711,8 @@ async fn setup<W, R>(
     rh: R,
     config: &Config,
     evt_queue_tx: async_channel::Sender<EventData>,
+    evt_broadcast_tx: broadcast::Sender<EventData>,
+    closed: Arc<OnceLock<CloseData>>,
 ) -> ActorRef<Coordinator<ActorRef<Writer<W>>>>
 where
     W: Send + Sync + 'static,
@@ -343,7 +735,12 @@ where
     let framed_reader = FramedRead::new(rh, codec);
 
     let writer = Writer::new(serializer_client, framed_writer);
-    let coordinator = Coordinator::spawn(Coordinator::new(Writer::spawn(writer), evt_queue_tx));
+    let coordinator = Coordinator::spawn(Coordinator::new(
+        Writer::spawn(writer),
+        evt_queue_tx,
+        evt_broadcast_tx,
+        closed,
+    ));
     let reader = Reader::new(deserialization_client, coordinator.clone());
     Reader::spawn(ReaderArgs {
         framed_reader,
@@ -375,16 +772,10 @@ mod macros {
                         .inner
                         .send_request(PayloadRequest::builder().event().$request_name(args).build())
                         .await?;
-                    if let Some(Data::$args_name(data)) = response.0.data {
-                        Ok(data)
-                    } else if let Some(Data::Error(error)) = response.0.data {
-                        Err(SdkClientError::ResponseError { error })
-                    } else {
-                        error!(
-                            "response should always have data but could not be found... panicking...; final response: {:?}",
-                            response
-                        );
-                        panic!("some form of data should always be returned...");
+                    match response.0.data {
+                        Some(Data::$args_name(data)) => Ok(data),
+                        Some(Data::Error(error)) => Err(SdkClientError::ResponseError { error }),
+                        _ => Err(SdkClientError::UnexpectedResponse(Box::new(response))),
                     }
                 }
             }
@@ -396,13 +787,24 @@ mod macros {
             $(#[$attr:meta])*
             $request_name: ident;
             $args_name: ident
+        ) => {
+            impl_request! {
+                $(#[$attr])*
+                pub fn $request_name;
+                $args_name
+            }
+        };
+        (
+            $(#[$attr:meta])*
+            $vis: vis fn $request_name: ident;
+            $args_name: ident
         ) => {
             paste::paste! {
                 $(#[$attr])*
                 /// # Errors
                 /// A [SdkClientError] is returned if the client fails to send the request or if the server
                 /// responds with an error
-                pub async fn $request_name(&self, args: [<$args_name Args>]) -> SdkClientResult<[<$args_name Data>]> {
+                $vis async fn $request_name(&self, args: [<$args_name Args>]) -> SdkClientResult<[<$args_name Data>]> {
                     if let Some(ref mgr) = self.token_manager
                     {
                         mgr.refresh_token().await?;
@@ -411,16 +813,10 @@ mod macros {
                         .inner
                         .send_request(PayloadRequest::builder().request(args).build())
                         .await?;
-                    if let Some(Data::$args_name(data)) = response.0.data {
-                        Ok(data)
-                    } else if let Some(Data::Error(error)) = response.0.data {
-                        Err(SdkClientError::ResponseError { error })
-                    } else {
-                        error!(
-                            "response should always have data but could not be found... panicking...; final response: {:?}",
-                            response
-                        );
-                        panic!("some form of data should always be returned...");
+                    match response.0.data {
+                        Some(Data::$args_name(data)) => Ok(data),
+                        Some(Data::Error(error)) => Err(SdkClientError::ResponseError { error }),
+                        _ => Err(SdkClientError::UnexpectedResponse(Box::new(response))),
                     }
                 }
             }
@@ -433,6 +829,9 @@ mod macros {
 use macros::impl_evt_req;
 use macros::impl_request;
 
+/// Boxed error used as the source of an [SdkClientError]
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
 /// An Error type for when making requests to the IPC server may fail
 #[derive(Debug, Error)]
 pub enum SdkClientError {
@@ -440,25 +839,202 @@ pub enum SdkClientError {
     #[error("failed to send the request!")]
     SendRequest(Option<PayloadRequest>),
     /// The request has been received, but the coordinator has failed to process it
-    #[error("internal server received the request, but failed to process it {0}")]
-    InternalCoordinator(String),
+    #[error("internal server received the request, but failed to process it")]
+    InternalCoordinator(#[source] BoxError),
     /// The request has been sent, but the client has failed to receive a response from the server
     /// in a timely manner
     #[error("response timeout from server!")]
     Timeout,
     /// The response sender has been dropped and the response is unrecoverable
-    #[error("server dropped response; response unrecoverable: {0}")]
-    ResponseDropped(String),
+    #[error("server dropped response; response unrecoverable")]
+    ResponseDropped(#[source] RecvError),
     /// The client failed to connect to the IPC server
-    #[error("client failed to connect to ipc {0}")]
-    ConnectionFailed(String),
+    #[error("client failed to connect to ipc")]
+    ConnectionFailed(#[source] BoxError),
     /// Configuration error
     #[error("failed to spawn client because of config: {error}")]
     ConfigFailed { config: Config, error: String },
     /// Response is an error
     #[error("response sent back an error")]
     ResponseError { error: Box<ErrorData> },
+    /// The response is missing data, carries data that does not belong to the request or data
+    /// that could not be deserialized, which is kept as [Data::Raw]; the raw response is returned
+    #[error("server sent back an unexpected response")]
+    UnexpectedResponse(Box<PayloadResponse>),
+    /// Discord closed the IPC connection; the [RpcCloseCode] tells whether reconnecting may
+    /// succeed
+    #[error("discord closed the ipc connection")]
+    ConnectionClosed(Box<CloseData>),
+    /// The event queue has been closed and no more events can be read
+    #[error("event queue is closed")]
+    EventQueueClosed(#[source] async_channel::RecvError),
     /// OAuth2 Error
     #[error("oauth2 error: {0}")]
     OAuth2(#[from] OAuth2Error),
 }
+
+impl SdkClientError {
+    /// Returns the [RpcErrorCode] if the server responded with an error
+    pub fn rpc_error_code(&self) -> Option<RpcErrorCode> {
+        if let SdkClientError::ResponseError { error } = self {
+            error.code
+        } else {
+            None
+        }
+    }
+
+    /// Returns the [RpcCloseCode] if Discord closed the connection
+    pub fn close_code(&self) -> Option<RpcCloseCode> {
+        if let SdkClientError::ConnectionClosed(data) = self {
+            data.code
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use serde_json::{Value, json};
+
+    use crate::{
+        link::DiscordLink,
+        payload::{Data, GetGuildsArgs, RpcCloseCode, common::opcode::Opcode},
+        test_util::mock_client,
+    };
+
+    use super::SdkClientError;
+
+    #[tokio::test]
+    async fn malformed_response_is_returned_to_the_request() {
+        let (client, mut discord) = mock_client().await;
+        let request = {
+            let client = client.clone();
+            tokio::spawn(async move { client.get_guilds(GetGuildsArgs::default()).await })
+        };
+        let payload = discord.recv().await;
+        discord.respond(&payload, json!({"guilds": "none"})).await;
+        let err = request.await.unwrap().unwrap_err();
+        let SdkClientError::UnexpectedResponse(response) = err else {
+            panic!("expected an unexpected response, got {err:?}");
+        };
+        assert_eq!(
+            response.0.data,
+            Some(Data::Raw(Box::new(json!({"guilds": "none"}))))
+        );
+    }
+
+    #[tokio::test]
+    async fn connection_closed_by_discord() {
+        let (client, mut discord) = mock_client().await;
+        let request = {
+            let client = client.clone();
+            tokio::spawn(async move { client.get_guilds(GetGuildsArgs::default()).await })
+        };
+        let payload = discord.recv().await;
+        assert_eq!(payload["cmd"], "GET_GUILDS");
+        discord
+            .send(
+                Opcode::Close,
+                json!({"code": 4003, "message": "Token revoked"}),
+            )
+            .await;
+        let err = request.await.unwrap().unwrap_err();
+        assert_eq!(err.close_code(), Some(RpcCloseCode::TokenRevoked));
+        assert!(err.close_code().unwrap().requires_reauth());
+        assert!(matches!(
+            client.read_event_queue().await,
+            Err(SdkClientError::ConnectionClosed(_))
+        ));
+        assert!(matches!(
+            client.get_guilds(GetGuildsArgs::default()).await,
+            Err(SdkClientError::ConnectionClosed(_))
+        ));
+    }
+
+    /// Opens `link` while Discord answers the requests in order with `responses` and returns the
+    /// written requests
+    async fn open_link(link: &str, responses: Vec<Value>) -> Vec<Value> {
+        let (client, mut discord) = mock_client().await;
+        let link = link.parse::<DiscordLink>().unwrap();
+        let open = tokio::spawn(async move { client.open_link(&link).await });
+        let mut requests = Vec::new();
+        for data in responses {
+            let request = discord.recv().await;
+            discord.respond(&request, data).await;
+            requests.push(request);
+        }
+        open.await.unwrap().unwrap();
+        requests
+    }
+
+    #[tokio::test]
+    async fn open_message_link() {
+        let requests = open_link(
+            "https://discord.com/channels/199737254929760256/199737254929760257/199743874640379904",
+            vec![Value::Null],
+        )
+        .await;
+        assert_eq!(requests[0]["cmd"], "DEEP_LINK");
+        assert_eq!(
+            requests[0]["args"],
+            json!({
+                "type": "CHANNEL",
+                "params": {
+                    "guildId": "199737254929760256",
+                    "channelId": "199737254929760257",
+                    "messageId": "199743874640379904"
+                }
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn open_voice_channel_link() {
+        let channel = json!({"id": "199737254929760257", "name": "General", "type": 2, "guild_id": "199737254929760256"});
+        let requests = open_link(
+            "https://discord.com/channels/199737254929760256/199737254929760257",
+            vec![channel.clone(), channel],
+        )
+        .await;
+        let commands = requests
+            .iter()
+            .map(|request| request["cmd"].clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            commands,
+            [json!("GET_CHANNEL"), json!("SELECT_VOICE_CHANNEL")]
+        );
+        assert_eq!(
+            requests[0]["args"],
+            json!({"channel_id": "199737254929760257"})
+        );
+        assert_eq!(
+            requests[1]["args"]["channel_id"],
+            json!("199737254929760257")
+        );
+    }
+
+    #[tokio::test]
+    async fn open_text_channel_link() {
+        let channel = json!({"id": "199737254929760257", "name": "general", "type": 0, "guild_id": "199737254929760256"});
+        let requests = open_link(
+            "https://discord.com/channels/199737254929760256/199737254929760257",
+            vec![channel.clone(), channel],
+        )
+        .await;
+        let commands = requests
+            .iter()
+            .map(|request| request["cmd"].clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            commands,
+            [json!("GET_CHANNEL"), json!("SELECT_TEXT_CHANNEL")]
+        );
+        assert_eq!(
+            requests[1]["args"]["channel_id"],
+            json!("199737254929760257")
+        );
+    }
+}
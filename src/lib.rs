@@ -10,13 +10,22 @@
 #![deny(unsafe_code, unreachable_pub)]
 pub mod client;
 pub mod config;
+pub mod link;
+pub mod lobby;
 pub mod payload;
+pub mod presence;
+#[cfg(target_os = "linux")]
+pub mod register;
+pub mod secret;
 
 mod actors;
 mod codec;
 mod conn;
 mod oauth2;
 mod pool;
+mod task;
+#[cfg(all(test, unix))]
+mod test_util;
 
 pub use conn::ConnectionError;
 pub use pool::SerdeProcessingError;
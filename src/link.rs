@@ -0,0 +1,193 @@
+//! Parsing of Discord links into targets the client can open.
+//!
+//! ```
+//! use sdkcord::link::DiscordLink;
+//!
+//! let link = "https://discord.gg/discord-developers".parse::<DiscordLink>().unwrap();
+//! assert_eq!(
+//!     link,
+//!     DiscordLink::Invite {
+//!         code: "discord-developers".to_string()
+//!     }
+//! );
+//! ```
+//!
+//! A parsed link can be opened with [SdkClient::open_link][crate::client::SdkClient::open_link].
+use std::str::FromStr;
+
+use thiserror::Error;
+use url::Url;
+
+/// A typed target of a Discord link
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum DiscordLink {
+    /// `discord.com/channels/<guild>/<channel>[/<message>]`; `guild_id` is [None] for direct
+    /// messages (`@me`)
+    Channel {
+        guild_id: Option<String>,
+        channel_id: String,
+        message_id: Option<String>,
+    },
+    /// `discord.gg/<code>` or `discord.com/invite/<code>`
+    Invite { code: String },
+    /// `discord.new/<code>` or `discord.com/template/<code>`
+    GuildTemplate { code: String },
+}
+
+impl FromStr for DiscordLink {
+    type Err = LinkError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let url = if s.contains("://") {
+            Url::parse(s)
+        } else {
+            Url::parse(&format!("https://{s}"))
+        }
+        .map_err(|_| LinkError::InvalidUrl(s.to_string()))?;
+        let host = url
+            .host_str()
+            .ok_or_else(|| LinkError::InvalidUrl(s.to_string()))?;
+        let host = host.strip_prefix("www.").unwrap_or(host);
+        let segments = url
+            .path_segments()
+            .map(|segments| segments.filter(|segment| !segment.is_empty()).collect())
+            .unwrap_or_else(Vec::new);
+        let unsupported = || LinkError::Unsupported(s.to_string());
+        match host {
+            "discord.gg" => match segments.as_slice() {
+                [code] => Ok(DiscordLink::Invite {
+                    code: code.to_string(),
+                }),
+                _ => Err(unsupported()),
+            },
+            "discord.new" => match segments.as_slice() {
+                [code] => Ok(DiscordLink::GuildTemplate {
+                    code: code.to_string(),
+                }),
+                _ => Err(unsupported()),
+            },
+            "discord.com" | "ptb.discord.com" | "canary.discord.com" | "discordapp.com" => {
+                match segments.as_slice() {
+                    ["invite", code] => Ok(DiscordLink::Invite {
+                        code: code.to_string(),
+                    }),
+                    ["template", code] => Ok(DiscordLink::GuildTemplate {
+                        code: code.to_string(),
+                    }),
+                    ["channels", guild_id, channel_id, rest @ ..] if rest.len() <= 1 => {
+                        let guild_id = (*guild_id != "@me")
+                            .then(|| snowflake(guild_id))
+                            .transpose()?;
+                        let message_id = rest
+                            .first()
+                            .map(|message_id| snowflake(message_id))
+                            .transpose()?;
+                        Ok(DiscordLink::Channel {
+                            guild_id,
+                            channel_id: snowflake(channel_id)?,
+                            message_id,
+                        })
+                    }
+                    _ => Err(unsupported()),
+                }
+            }
+            _ => Err(unsupported()),
+        }
+    }
+}
+
+/// Checks that an id of a link is a numeric snowflake before it is sent in a request
+fn snowflake(id: &str) -> Result<String, LinkError> {
+    if id.is_empty() || !id.bytes().all(|byte| byte.is_ascii_digit()) {
+        return Err(LinkError::InvalidSnowflake(id.to_string()));
+    }
+    Ok(id.to_string())
+}
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Error)]
+pub enum LinkError {
+    #[error("{0} is not a valid url")]
+    InvalidUrl(String),
+    #[error("{0} is not a supported Discord link")]
+    Unsupported(String),
+    #[error("{0} is not a valid snowflake id")]
+    InvalidSnowflake(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::{DiscordLink, LinkError};
+
+    #[test]
+    fn parse_channel_links() {
+        let link =
+            "https://discord.com/channels/199737254929760256/199737254929760257/199743874640379904"
+                .parse::<DiscordLink>()
+                .unwrap();
+        assert_eq!(
+            link,
+            DiscordLink::Channel {
+                guild_id: Some("199737254929760256".to_string()),
+                channel_id: "199737254929760257".to_string(),
+                message_id: Some("199743874640379904".to_string()),
+            }
+        );
+        let link = "canary.discord.com/channels/@me/199737254929760257"
+            .parse::<DiscordLink>()
+            .unwrap();
+        assert_eq!(
+            link,
+            DiscordLink::Channel {
+                guild_id: None,
+                channel_id: "199737254929760257".to_string(),
+                message_id: None,
+            }
+        );
+    }
+
+    #[test]
+    fn parse_invite_and_template_links() {
+        for link in ["discord.gg/abc123", "https://discord.com/invite/abc123/"] {
+            assert_eq!(
+                link.parse::<DiscordLink>().unwrap(),
+                DiscordLink::Invite {
+                    code: "abc123".to_string()
+                }
+            );
+        }
+        assert_eq!(
+            "https://discord.new/hgM48av5Q69A"
+                .parse::<DiscordLink>()
+                .unwrap(),
+            DiscordLink::GuildTemplate {
+                code: "hgM48av5Q69A".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn parse_unsupported_links() {
+        assert!(matches!(
+            "https://example.com/channels/1/2".parse::<DiscordLink>(),
+            Err(LinkError::Unsupported(_))
+        ));
+        assert!(matches!(
+            "https://discord.com/channels/1".parse::<DiscordLink>(),
+            Err(LinkError::Unsupported(_))
+        ));
+        assert_eq!(
+            "https://discord.com/channels/@me/general".parse::<DiscordLink>(),
+            Err(LinkError::InvalidSnowflake("general".to_string()))
+        );
+        assert_eq!(
+            "https://discord.com/channels/abc/2/3".parse::<DiscordLink>(),
+            Err(LinkError::InvalidSnowflake("abc".to_string()))
+        );
+        assert!(matches!(
+            "https://".parse::<DiscordLink>(),
+            Err(LinkError::InvalidUrl(_))
+        ));
+    }
+}
@@ -0,0 +1,415 @@
+//! Lobby state tracking on top of the [SdkClient].
+//!
+//! The [LobbyManager] keeps track of the lobbies the current user created or connected to. Once
+//! subscribed to a lobby with [LobbyManager::subscribe], its lobby and member updates are read
+//! from [SdkClient::event_stream] and reflected in the tracked state, while the events stay
+//! available through [SdkClient::read_event_queue].
+use std::{collections::HashMap, sync::Arc};
+
+use tokio::sync::{
+    RwLock,
+    broadcast::{self, error::RecvError},
+};
+use tracing::warn;
+
+use crate::{
+    client::{SdkClient, SdkClientError},
+    payload::{
+        ConnectToLobbyArgs, CreateLobbyArgs, DeleteLobbyArgs, DisconnectFromLobbyArgs, EventData,
+        LobbyDeleteArgs, LobbyMemberConnectArgs, LobbyMemberDisconnectArgs, LobbyMemberEvent,
+        LobbyMemberUpdateArgs, LobbyMessageArgs, LobbyUpdateArgs, SendToLobbyArgs, UpdateLobbyArgs,
+        UpdateLobbyMemberArgs,
+        common::lobby::{Lobby, LobbyId, LobbyMember, LobbyMetadata},
+    },
+    task::AbortOnDrop,
+};
+
+/// Manages the lobbies of the current user
+///
+/// The events are tracked until the last clone of the manager is dropped.
+#[derive(Debug, Clone)]
+pub struct LobbyManager {
+    client: SdkClient,
+    lobbies: Arc<RwLock<HashMap<String, Lobby>>>,
+    _events: Arc<AbortOnDrop>,
+}
+
+impl LobbyManager {
+    /// Creates a manager tracking the lobby events of `client`
+    ///
+    /// Must be called within a tokio runtime.
+    pub fn new(client: SdkClient) -> Self {
+        let lobbies = Arc::new(RwLock::new(HashMap::new()));
+        let events = AbortOnDrop::spawn(track_events(client.event_stream(), lobbies.clone()));
+        Self {
+            client,
+            lobbies,
+            _events: Arc::new(events),
+        }
+    }
+
+    /// Subscribe to all of the events of the lobby with the given id
+    ///
+    /// # Errors
+    /// A [SdkClientError] is returned if any of the subscriptions fails
+    pub async fn subscribe(&self, lobby_id: &str) -> Result<(), SdkClientError> {
+        let lobby_id = LobbyId::from(lobby_id);
+        self.client
+            .subscribe(LobbyUpdateArgs(lobby_id.clone()))
+            .await?;
+        self.client
+            .subscribe(LobbyDeleteArgs(lobby_id.clone()))
+            .await?;
+        self.client
+            .subscribe(LobbyMemberConnectArgs(lobby_id.clone()))
+            .await?;
+        self.client
+            .subscribe(LobbyMemberUpdateArgs(lobby_id.clone()))
+            .await?;
+        self.client
+            .subscribe(LobbyMemberDisconnectArgs(lobby_id.clone()))
+            .await?;
+        self.client.subscribe(LobbyMessageArgs(lobby_id)).await?;
+        Ok(())
+    }
+
+    /// Create a lobby and start tracking it
+    ///
+    /// # Errors
+    /// A [SdkClientError] is returned if the request fails
+    pub async fn create_lobby(&self, args: CreateLobbyArgs) -> Result<Lobby, SdkClientError> {
+        let lobby = self.client.create_lobby(args).await?.0;
+        self.insert(lobby.clone()).await;
+        Ok(lobby)
+    }
+
+    /// Update a lobby; the tracked state is updated once the LOBBY_UPDATE event is handled
+    ///
+    /// # Errors
+    /// A [SdkClientError] is returned if the request fails
+    pub async fn update_lobby(&self, args: UpdateLobbyArgs) -> Result<(), SdkClientError> {
+        self.client.update_lobby(args).await?;
+        Ok(())
+    }
+
+    /// Delete a lobby and stop tracking it
+    ///
+    /// # Errors
+    /// A [SdkClientError] is returned if the request fails
+    pub async fn delete_lobby(&self, id: &str) -> Result<(), SdkClientError> {
+        self.client
+            .delete_lobby(DeleteLobbyArgs::builder().id(id).build())
+            .await?;
+        self.lobbies.write().await.remove(id);
+        Ok(())
+    }
+
+    /// Connect to a lobby and start tracking it
+    ///
+    /// # Errors
+    /// A [SdkClientError] is returned if the request fails
+    pub async fn connect(&self, id: &str, secret: &str) -> Result<Lobby, SdkClientError> {
+        let lobby = self
+            .client
+            .connect_to_lobby(ConnectToLobbyArgs::builder().id(id).secret(secret).build())
+            .await?
+            .0;
+        self.insert(lobby.clone()).await;
+        Ok(lobby)
+    }
+
+    /// Disconnect from a lobby and stop tracking it
+    ///
+    /// # Errors
+    /// A [SdkClientError] is returned if the request fails
+    pub async fn disconnect(&self, id: &str) -> Result<(), SdkClientError> {
+        self.client
+            .disconnect_from_lobby(DisconnectFromLobbyArgs::builder().id(id).build())
+            .await?;
+        self.lobbies.write().await.remove(id);
+        Ok(())
+    }
+
+    /// Send a message to every member of a lobby
+    ///
+    /// # Errors
+    /// A [SdkClientError] is returned if the request fails
+    pub async fn send(
+        &self,
+        lobby_id: &str,
+        data: impl Into<String>,
+    ) -> Result<(), SdkClientError> {
+        self.client
+            .send_to_lobby(
+                SendToLobbyArgs::builder()
+                    .lobby_id(lobby_id)
+                    .data(data)
+                    .build(),
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Replace the metadata of a lobby member
+    ///
+    /// # Errors
+    /// A [SdkClientError] is returned if the request fails
+    pub async fn update_member(
+        &self,
+        lobby_id: &str,
+        user_id: &str,
+        metadata: LobbyMetadata,
+    ) -> Result<(), SdkClientError> {
+        self.client
+            .update_lobby_member(
+                UpdateLobbyMemberArgs::builder()
+                    .lobby_id(lobby_id)
+                    .user_id(user_id)
+                    .metadata(metadata)
+                    .build(),
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Returns the tracked lobby with the given id
+    pub async fn lobby(&self, id: &str) -> Option<Lobby> {
+        self.lobbies.read().await.get(id).cloned()
+    }
+
+    /// Returns all of the tracked lobbies
+    pub async fn lobbies(&self) -> Vec<Lobby> {
+        self.lobbies.read().await.values().cloned().collect()
+    }
+
+    /// Returns the members of the tracked lobby with the given id
+    pub async fn members(&self, id: &str) -> Vec<LobbyMember> {
+        self.lobbies
+            .read()
+            .await
+            .get(id)
+            .and_then(|lobby| lobby.members.clone())
+            .unwrap_or_default()
+    }
+
+    async fn insert(&self, lobby: Lobby) {
+        if let Some(id) = lobby.id.clone() {
+            self.lobbies.write().await.insert(id, lobby);
+        }
+    }
+}
+
+async fn track_events(
+    mut events: broadcast::Receiver<EventData>,
+    lobbies: Arc<RwLock<HashMap<String, Lobby>>>,
+) {
+    loop {
+        match events.recv().await {
+            Ok(event) => {
+                apply_event(&mut *lobbies.write().await, &event);
+            }
+            Err(RecvError::Lagged(skipped)) => {
+                warn!("lobby state missed {skipped} events and may be out of date");
+            }
+            Err(RecvError::Closed) => break,
+        }
+    }
+}
+
+/// Updates the tracked state from an event; returns `true` if the state changed
+fn apply_event(lobbies: &mut HashMap<String, Lobby>, event: &EventData) -> bool {
+    match event {
+        EventData::LobbyUpdate(data) => {
+            let mut lobby = data.0.clone();
+            let Some(id) = lobby.id.clone() else {
+                return false;
+            };
+            if let Some(tracked) = lobbies.get_mut(&id) {
+                if lobby.members.is_none() {
+                    lobby.members = tracked.members.take();
+                }
+                *tracked = lobby;
+                true
+            } else {
+                false
+            }
+        }
+        EventData::LobbyDelete(data) => data
+            .id
+            .as_ref()
+            .is_some_and(|id| lobbies.remove(id).is_some()),
+        EventData::LobbyMemberConnect(data) => upsert_member(lobbies, &data.0),
+        EventData::LobbyMemberUpdate(data) => upsert_member(lobbies, &data.0),
+        EventData::LobbyMemberDisconnect(data) => {
+            let Some((members, user_id)) = tracked_members(lobbies, &data.0) else {
+                return false;
+            };
+            let len = members.len();
+            members.retain(|member| member.user_id() != Some(user_id.as_str()));
+            members.len() != len
+        }
+        _ => false,
+    }
+}
+
+fn upsert_member(lobbies: &mut HashMap<String, Lobby>, event: &LobbyMemberEvent) -> bool {
+    let Some((members, user_id)) = tracked_members(lobbies, event) else {
+        return false;
+    };
+    let Some(member) = event.member.clone() else {
+        return false;
+    };
+    match members
+        .iter_mut()
+        .find(|member| member.user_id() == Some(user_id.as_str()))
+    {
+        Some(tracked) => *tracked = member,
+        None => members.push(member),
+    }
+    true
+}
+
+fn tracked_members<'a>(
+    lobbies: &'a mut HashMap<String, Lobby>,
+    event: &LobbyMemberEvent,
+) -> Option<(&'a mut Vec<LobbyMember>, String)> {
+    let lobby = lobbies.get_mut(event.lobby_id.as_deref()?)?;
+    let user_id = event.member.as_ref()?.user_id()?.to_string();
+    Some((lobby.members.get_or_insert_with(Vec::new), user_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use pretty_assertions::assert_eq;
+
+    use crate::payload::{
+        EventData, LobbyDeleteData, LobbyMemberConnectData, LobbyMemberDisconnectData,
+        LobbyUpdateData,
+    };
+
+    use super::apply_event;
+    #[cfg(unix)]
+    use {
+        super::LobbyManager,
+        crate::{payload::common::opcode::Opcode, test_util::mock_client},
+        serde_json::json,
+        std::time::Duration,
+    };
+
+    fn lobbies() -> HashMap<String, crate::payload::common::lobby::Lobby> {
+        let payload = r##"{"capacity":4,"id":"580087718095519744","locked":false,"members":[{"metadata":{},"user":{"avatar":null,"discriminator":"0","id":"53908232506183680","username":"Mason"}}],"metadata":{},"owner_id":"53908232506183680","secret":"a4ec5c2d0c3fb1f6","type":1}"##;
+        let lobby = serde_json::from_str::<LobbyUpdateData>(payload).unwrap().0;
+        HashMap::from([(lobby.id.clone().unwrap(), lobby)])
+    }
+
+    #[test]
+    fn track_lobby_members() {
+        let mut lobbies = lobbies();
+        let payload = r##"{"lobby_id":"580087718095519744","member":{"metadata":{},"user":{"avatar":null,"discriminator":"0","id":"158284148040138752","username":"day2"}}}"##;
+        let connect = serde_json::from_str::<LobbyMemberConnectData>(payload).unwrap();
+        assert!(apply_event(
+            &mut lobbies,
+            &EventData::LobbyMemberConnect(Box::new(connect))
+        ));
+        assert_eq!(
+            lobbies["580087718095519744"]
+                .members
+                .as_ref()
+                .unwrap()
+                .len(),
+            2
+        );
+        let disconnect = serde_json::from_str::<LobbyMemberDisconnectData>(payload).unwrap();
+        assert!(apply_event(
+            &mut lobbies,
+            &EventData::LobbyMemberDisconnect(Box::new(disconnect))
+        ));
+        assert_eq!(
+            lobbies["580087718095519744"]
+                .members
+                .as_ref()
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn track_lobby_update_and_delete() {
+        let mut lobbies = lobbies();
+        let payload = r##"{"capacity":8,"id":"580087718095519744","locked":true,"metadata":{"map":"dust2"},"owner_id":"53908232506183680","type":1}"##;
+        let update = serde_json::from_str::<LobbyUpdateData>(payload).unwrap();
+        assert!(apply_event(
+            &mut lobbies,
+            &EventData::LobbyUpdate(Box::new(update))
+        ));
+        let lobby = &lobbies["580087718095519744"];
+        assert_eq!(lobby.capacity, Some(8));
+        assert_eq!(lobby.members.as_ref().unwrap().len(), 1);
+        let delete =
+            serde_json::from_str::<LobbyDeleteData>(r##"{"id":"580087718095519744","reason":0}"##)
+                .unwrap();
+        assert!(apply_event(
+            &mut lobbies,
+            &EventData::LobbyDelete(Box::new(delete))
+        ));
+        assert!(lobbies.is_empty());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn track_events_of_subscribed_lobby() {
+        let (client, mut discord) = mock_client().await;
+        let manager = LobbyManager::new(client);
+        let connect = {
+            let manager = manager.clone();
+            tokio::spawn(async move {
+                manager
+                    .connect("580087718095519744", "a4ec5c2d0c3fb1f6")
+                    .await
+            })
+        };
+        let request = discord.recv().await;
+        assert_eq!(request["cmd"], "CONNECT_TO_LOBBY");
+        let lobby = serde_json::from_str::<serde_json::Value>(
+            r##"{"capacity":4,"id":"580087718095519744","locked":false,"members":[{"metadata":{},"user":{"avatar":null,"discriminator":"0","id":"53908232506183680","username":"Mason"}}],"metadata":{},"owner_id":"53908232506183680","secret":"a4ec5c2d0c3fb1f6","type":1}"##,
+        )
+        .unwrap();
+        discord.respond(&request, lobby).await;
+        connect.await.unwrap().unwrap();
+
+        let subscribe = {
+            let manager = manager.clone();
+            tokio::spawn(async move { manager.subscribe("580087718095519744").await })
+        };
+        for _ in 0..6 {
+            let request = discord.recv().await;
+            assert_eq!(request["cmd"], "SUBSCRIBE");
+            assert_eq!(request["args"], json!({"lobby_id": "580087718095519744"}));
+            discord
+                .respond(&request, json!({"evt": request["evt"]}))
+                .await;
+        }
+        subscribe.await.unwrap().unwrap();
+
+        let member = serde_json::from_str::<serde_json::Value>(
+            r##"{"lobby_id":"580087718095519744","member":{"metadata":{},"user":{"avatar":null,"discriminator":"0","id":"158284148040138752","username":"day2"}}}"##,
+        )
+        .unwrap();
+        discord
+            .send(
+                Opcode::Frame,
+                json!({"cmd": "DISPATCH", "evt": "LOBBY_MEMBER_CONNECT", "data": member}),
+            )
+            .await;
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while manager.members("580087718095519744").await.len() != 2 {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+    }
+}
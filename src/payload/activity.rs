@@ -2,10 +2,10 @@ use bon::Builder;
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
 
-use crate::payload::common::activity::ActivityRequest;
+use crate::payload::{EmptyBracket, common::activity::ActivityRequest};
 
 use super::{
-    common::activity::Activity,
+    common::{activity::Activity, user::User},
     macros::{impl_empty_args_type, impl_event_args_type, impl_request_args_type},
 };
 
@@ -20,47 +20,65 @@ pub struct SetActivityArgs {
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
 pub struct SetActivityData(pub Option<Activity>);
 
-#[skip_serializing_none]
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash, Builder)]
-#[cfg(feature = "untested")]
 pub struct SendActivityJoinInviteArgs {
     #[builder(into)]
-    user_id: Option<String>,
+    user_id: String,
 }
 
-#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct SendActivityJoinInviteData(pub Option<EmptyBracket>);
+
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash, Builder)]
-#[cfg(feature = "untested")]
 pub struct CloseActivityRequestArgs {
     #[builder(into)]
-    user_id: Option<String>,
+    user_id: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct CloseActivityRequestData(pub Option<EmptyBracket>);
+
+/// Data sent when the user clicks "Join" on the activity; `secret` is the [join
+/// secret][crate::payload::common::activity::Secrets::join] of the activity
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct ActivityJoinData {
+    pub secret: Option<String>,
+}
+
+/// Data sent when the user clicks "Spectate" on the activity; `secret` is the [spectate
+/// secret][crate::payload::common::activity::Secrets::spectate] of the activity
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct ActivitySpectateData {
+    pub secret: Option<String>,
+}
+
+/// Data sent when a user asks to join the activity of the current user
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct ActivityJoinRequestData {
+    pub user: Option<User>,
 }
 
 impl_request_args_type!(SetActivity);
-#[cfg(feature = "untested")]
 impl_request_args_type!(SendActivityJoinInvite);
-#[cfg(feature = "untested")]
 impl_request_args_type!(CloseActivityRequest);
 
-#[cfg(feature = "untested")]
 impl_empty_args_type!(ActivityJoin);
-#[cfg(feature = "untested")]
 impl_empty_args_type!(ActivitySpectate);
-#[cfg(feature = "untested")]
 impl_empty_args_type!(ActivityJoinRequest);
 
-#[cfg(feature = "untested")]
 impl_event_args_type!(ActivityJoin);
-#[cfg(feature = "untested")]
 impl_event_args_type!(ActivitySpectate);
-#[cfg(feature = "untested")]
 impl_event_args_type!(ActivityJoinRequest);
 
 #[cfg(test)]
 mod tests {
+    use pretty_assertions::assert_eq;
+
     use crate::payload::common::activity::{Activity, ActivityType};
 
-    use super::SetActivityArgs;
+    use super::{
+        ActivityJoinData, ActivityJoinRequestData, SendActivityJoinInviteArgs, SetActivityArgs,
+    };
 
     #[test]
     fn construct_set_activity_args() {
@@ -69,12 +87,39 @@ mod tests {
             .activity(
                 Activity::request_builder()
                     .activity_type(ActivityType::Watching)
-                    .timestamps((12, 32))
-                    .call(),
+                    .timestamps((1_507_665_886_000, 1_507_666_886_000))
+                    .call()
+                    .unwrap(),
             )
             .build();
         let sca = serde_json::to_string(&sca).unwrap();
         assert!(sca.contains("\"type\":3"));
         assert!(sca.contains("\"pid\":12"));
     }
+
+    #[test]
+    fn construct_send_activity_join_invite_args() {
+        let args = SendActivityJoinInviteArgs::builder()
+            .user_id("53908232506183680")
+            .build();
+        let args = serde_json::to_string(&args).unwrap();
+        assert_eq!(args, r#"{"user_id":"53908232506183680"}"#);
+    }
+
+    #[test]
+    fn deserialize_activity_join() {
+        let payload = r##"{"secret":"025ed05c71f639de8bfaa0d679d7c94b2fdce12f"}"##;
+        let data = serde_json::from_str::<ActivityJoinData>(payload).unwrap();
+        assert_eq!(
+            data.secret,
+            Some("025ed05c71f639de8bfaa0d679d7c94b2fdce12f".to_string())
+        );
+    }
+
+    #[test]
+    fn deserialize_activity_join_request() {
+        let payload = r##"{"user":{"id":"53908232506183680","username":"Mason","discriminator":"1337","avatar":"a_bab14f271d565501444b2ca3be944b25"}}"##;
+        let data = serde_json::from_str::<ActivityJoinRequestData>(payload).unwrap();
+        assert_eq!(data.user.unwrap().id, Some("53908232506183680".to_string()));
+    }
 }
@@ -1,6 +1,6 @@
 use std::{collections::HashSet, hash::RandomState, iter::FromIterator};
 
-use bon::{Builder, builder};
+use bon::Builder;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
@@ -5,7 +5,10 @@ use serde_with::skip_serializing_none;
 use crate::payload::common::{channel::impl_channel_id_type, guild::impl_guild_id_type};
 
 use super::{
-    common::channel::{Channel, ChannelResponse},
+    common::{
+        channel::{Channel, ChannelResponse},
+        permission::Permissions,
+    },
     macros::{impl_empty_args_type, impl_event_args_type, impl_request_args_type},
 };
 
@@ -31,12 +34,50 @@ pub struct GetChannelsData {
     pub channels: Vec<Channel>,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct GetChannelPermissionsData {
+    pub permissions: Option<Permissions>,
+}
+
 impl_empty_args_type!(ChannelCreate);
 impl_channel_id_type!(GetChannelArgs);
+impl_channel_id_type!(GetChannelPermissionsArgs);
 impl_guild_id_type!(GetChannelsArgs);
 
 impl_request_args_type!(GetChannel);
 impl_request_args_type!(GetChannels);
 impl_request_args_type!(SelectTextChannel);
+impl_request_args_type!(GetChannelPermissions);
 
 impl_event_args_type!(ChannelCreate);
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use crate::payload::common::permission::Permissions;
+
+    use super::GetChannelPermissionsData;
+
+    #[test]
+    fn deserialize_get_channel_permissions() {
+        let payload = r##"{"permissions":"3148800"}"##;
+        let data = serde_json::from_str::<GetChannelPermissionsData>(payload).unwrap();
+        let permissions = data.permissions.unwrap();
+        assert_eq!(
+            permissions,
+            Permissions::VIEW_CHANNEL
+                | Permissions::SEND_MESSAGES
+                | Permissions::CONNECT
+                | Permissions::SPEAK
+        );
+        assert!(permissions.can_speak());
+        assert!(permissions.can_send_messages());
+        let payload = serde_json::to_string(&GetChannelPermissionsData {
+            permissions: Some(Permissions::CONNECT),
+        })
+        .unwrap();
+        assert_eq!(payload, r##"{"permissions":"1048576"}"##);
+        assert!(!Permissions::CONNECT.can_speak());
+    }
+}
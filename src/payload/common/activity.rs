@@ -1,8 +1,12 @@
-use bon::{Builder, bon, builder};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use bon::{Builder, bon};
 use serde::{Deserialize, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 use serde_with::skip_serializing_none;
 use strum_macros::{Display, EnumString};
+use thiserror::Error;
+use url::Url;
 
 #[skip_serializing_none]
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
@@ -15,7 +19,10 @@ pub struct Activity {
     pub timestamps: Option<Timestamps>,
     pub application_id: Option<String>,
     pub details: Option<String>,
+    pub details_url: Option<String>,
     pub state: Option<String>,
+    pub state_url: Option<String>,
+    pub status_display_type: Option<StatusDisplayType>,
     pub emoji: Option<Emoji>,
     pub party: Option<Party>,
     pub assets: Option<Assets>,
@@ -26,61 +33,239 @@ pub struct Activity {
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
-pub struct ActivityRequest(Activity);
+#[serde(transparent)]
+pub struct ActivityRequest {
+    activity: Activity,
+    /// Whether the request was built with `skip_validation`
+    #[serde(skip)]
+    skip_validation: bool,
+}
 
 #[bon]
 impl Activity {
+    /// Builds an [ActivityRequest] to be sent with a
+    /// [SetActivityArgs][crate::payload::SetActivityArgs]
+    ///
+    /// `name` overrides the application name shown in the status, `url` is the stream url shown
+    /// for [ActivityType::Streaming] and Discord displays at most 2 `buttons`.
+    ///
+    /// # Errors
+    /// An [ActivityValidationError] is returned if the activity breaks one of the rules checked by
+    /// [ActivityRequest::validate]. Set `skip_validation` to send the activity as is.
     #[builder]
-    pub const fn request_builder(
+    pub fn request_builder(
         #[builder(into)] activity_type: ActivityType,
+        #[builder(into)] name: Option<String>,
+        #[builder(into)] url: Option<String>,
         #[builder(into)] timestamps: Option<Timestamps>,
         #[builder(into)] details: Option<String>,
+        #[builder(into)] details_url: Option<String>,
         #[builder(into)] state: Option<String>,
+        #[builder(into)] state_url: Option<String>,
+        status_display_type: Option<StatusDisplayType>,
         #[builder(into)] party: Option<Party>,
         #[builder(into)] assets: Option<Assets>,
         #[builder(into)] secrets: Option<Secrets>,
         #[builder(into)] instance: Option<bool>,
-    ) -> ActivityRequest {
-        ActivityRequest(Activity {
-            name: None,
+        #[builder(into)] buttons: Option<Vec<Button>>,
+        #[builder(default)] skip_validation: bool,
+    ) -> Result<ActivityRequest, ActivityValidationError> {
+        let activity = Activity {
+            name,
             activity_type: Some(activity_type),
-            url: None,
+            url,
             created_at: None,
             timestamps,
             application_id: None,
             details,
+            details_url,
             state,
+            state_url,
+            status_display_type,
             emoji: None,
             party,
             assets,
             secrets,
             instance,
             flags: None,
-            buttons: None,
-        })
+            buttons,
+        };
+        if skip_validation {
+            return Ok(ActivityRequest::unchecked(activity));
+        }
+        ActivityRequest::try_from(activity)
     }
 }
 
+/// Smallest value accepted as a timestamp in milliseconds; anything below is most likely a
+/// timestamp in seconds
+const MIN_TIMESTAMP_MILLIS: u64 = 1_000_000_000_000;
+
+impl ActivityRequest {
+    /// Checks the activity against the rules enforced by Discord
+    ///
+    /// - `name`, `details`, `state` and the asset texts are between 2 and 128 characters long
+    /// - at most 2 buttons are set, with labels between 1 and 32 characters long and https urls
+    /// - buttons and secrets are not set at the same time
+    /// - the current party size is not larger than the max party size
+    /// - timestamps are unix timestamps in milliseconds
+    ///
+    /// # Errors
+    /// An [ActivityValidationError] naming the first offending field is returned
+    pub fn validate(&self) -> Result<(), ActivityValidationError> {
+        let activity = &self.activity;
+        check_length("name", activity.name.as_deref(), 2, 128)?;
+        check_length("details", activity.details.as_deref(), 2, 128)?;
+        check_length("state", activity.state.as_deref(), 2, 128)?;
+        if let Some(assets) = &activity.assets {
+            check_length("assets.large_text", assets.large_text.as_deref(), 2, 128)?;
+            check_length("assets.small_text", assets.small_text.as_deref(), 2, 128)?;
+        }
+        if let Some(buttons) = &activity.buttons {
+            if buttons.len() > 2 {
+                return Err(ActivityValidationError::TooManyButtons(buttons.len()));
+            }
+            if activity.secrets.is_some() {
+                return Err(ActivityValidationError::ButtonsWithSecrets);
+            }
+            for (index, button) in buttons.iter().enumerate() {
+                check_length("buttons.label", button.label.as_deref(), 1, 32)?;
+                let url = button.url.as_deref().unwrap_or_default();
+                if Url::parse(url).map_or(true, |url| url.scheme() != "https") {
+                    return Err(ActivityValidationError::InsecureButtonUrl {
+                        index,
+                        url: url.to_string(),
+                    });
+                }
+            }
+        }
+        if let Some([current, max]) = activity.party.as_ref().and_then(|party| party.size)
+            && current > max
+        {
+            return Err(ActivityValidationError::PartySize { current, max });
+        }
+        if let Some(timestamps) = activity.timestamps {
+            for (field, value) in [
+                ("timestamps.start", timestamps.start),
+                ("timestamps.end", timestamps.end),
+            ] {
+                if let Some(value) = value.filter(|value| *value < MIN_TIMESTAMP_MILLIS) {
+                    return Err(ActivityValidationError::TimestampNotMillis { field, value });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the activity that will be sent
+    pub const fn activity(&self) -> &Activity {
+        &self.activity
+    }
+
+    /// Consumes the request and returns its activity
+    pub fn into_activity(self) -> Activity {
+        self.activity
+    }
+
+    /// Wraps `activity` without checking it, like `skip_validation` of
+    /// [Activity::request_builder]
+    pub(crate) const fn unchecked(activity: Activity) -> Self {
+        Self {
+            activity,
+            skip_validation: true,
+        }
+    }
+
+    /// Whether the request was built with `skip_validation`
+    pub(crate) const fn skips_validation(&self) -> bool {
+        self.skip_validation
+    }
+}
+
+impl TryFrom<Activity> for ActivityRequest {
+    type Error = ActivityValidationError;
+
+    fn try_from(value: Activity) -> Result<Self, Self::Error> {
+        let request = ActivityRequest {
+            activity: value,
+            skip_validation: false,
+        };
+        request.validate()?;
+        Ok(request)
+    }
+}
+
+fn check_length(
+    field: &'static str,
+    value: Option<&str>,
+    min: usize,
+    max: usize,
+) -> Result<(), ActivityValidationError> {
+    match value.map(|value| value.chars().count()) {
+        Some(len) if len < min || len > max => Err(ActivityValidationError::InvalidLength {
+            field,
+            len,
+            min,
+            max,
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Error returned when an [ActivityRequest] breaks one of the rules enforced by Discord
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Error)]
+pub enum ActivityValidationError {
+    #[error("{field} must be between {min} and {max} characters long but is {len}")]
+    InvalidLength {
+        field: &'static str,
+        len: usize,
+        min: usize,
+        max: usize,
+    },
+    #[error("at most 2 buttons can be set but {0} were set")]
+    TooManyButtons(usize),
+    #[error("buttons and secrets can not be set at the same time")]
+    ButtonsWithSecrets,
+    #[error("button {index} must have an https url but has {url:?}")]
+    InsecureButtonUrl { index: usize, url: String },
+    #[error("party size {current} is larger than the max party size {max}")]
+    PartySize { current: u32, max: u32 },
+    #[error("{field} must be a unix timestamp in milliseconds but is {value}")]
+    TimestampNotMillis { field: &'static str, value: u64 },
+}
+
 /// Activity type
 ///
 /// When you set an activity, you can specify the type of activity you want. For example, in
 /// Discord, you can set your status to "Playing `APPLICATION_NAME`".
 ///
-/// The activity type `Streaming` and `Custom` are disabled because Discord does not allow using
-/// these activity types.
+/// `Streaming` requires a Twitch or YouTube `url` on the activity and `Custom` is only sent by
+/// Discord for the custom status of a user.
 #[derive(
     Debug, Copy, Clone, Deserialize_repr, Serialize_repr, PartialEq, Eq, Hash, EnumString, Display,
 )]
 #[repr(u8)]
 pub enum ActivityType {
     Playing = 0,
-    // Streaming = 1,
+    Streaming = 1,
     Listening = 2,
     Watching = 3,
-    // Custom = 4,
+    Custom = 4,
     Competing = 5,
 }
 
+/// Which field of the activity is shown in the status of the user, e.g. "Listening to
+/// `STATE`" instead of "Listening to `NAME`"
+#[derive(
+    Debug, Copy, Clone, Deserialize_repr, Serialize_repr, PartialEq, Eq, Hash, EnumString, Display,
+)]
+#[repr(u8)]
+pub enum StatusDisplayType {
+    Name = 0,
+    State = 1,
+    Details = 2,
+}
+
 #[skip_serializing_none]
 #[derive(Debug, Copy, Clone, Deserialize, Serialize, PartialEq, Eq, Hash, Builder)]
 pub struct Timestamps {
@@ -151,6 +336,30 @@ impl Button {
     }
 }
 
+impl Timestamps {
+    /// Timestamps showing the time elapsed since now
+    pub fn elapsed_since_now() -> Self {
+        Timestamps {
+            start: Some(unix_millis(SystemTime::now())),
+            end: None,
+        }
+    }
+
+    /// Timestamps showing the time remaining until `duration` from now has passed
+    pub fn ends_in(duration: Duration) -> Self {
+        Timestamps {
+            start: None,
+            end: Some(unix_millis(SystemTime::now()).saturating_add(duration.as_millis() as u64)),
+        }
+    }
+}
+
+/// Converts `time` to the unix timestamp in milliseconds used by [Timestamps]
+pub(crate) fn unix_millis(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map_or(0, |time| time.as_millis() as u64)
+}
+
 impl From<(u64, u64)> for Timestamps {
     fn from(value: (u64, u64)) -> Self {
         Timestamps {
@@ -162,7 +371,12 @@ impl From<(u64, u64)> for Timestamps {
 
 #[cfg(test)]
 mod tests {
-    use super::{Activity, ActivityType, Assets, Party, Secrets};
+    use pretty_assertions::assert_eq;
+
+    use super::{
+        Activity, ActivityType, ActivityValidationError, Assets, Button, MIN_TIMESTAMP_MILLIS,
+        Party, Secrets, StatusDisplayType, Timestamps,
+    };
 
     #[test]
     fn construct_activity_request() {
@@ -170,7 +384,7 @@ mod tests {
             .activity_type(ActivityType::Watching)
             .state("State1")
             .details("Details1")
-            .timestamps((10, 20))
+            .timestamps((1_507_665_886_000, 1_507_666_886_000))
             .assets(
                 Assets::builder()
                     .large_image("123")
@@ -187,8 +401,113 @@ mod tests {
                     .secrets_match("123")
                     .build(),
             )
-            .call();
+            .call()
+            .unwrap();
         let activity_request = serde_json::to_string(&activity_request).unwrap();
         assert!(activity_request.contains("\"details\":\"Details1\""));
     }
+
+    #[test]
+    fn construct_streaming_activity_request() {
+        let activity_request = Activity::request_builder()
+            .activity_type(ActivityType::Streaming)
+            .name("Speedrun")
+            .url("https://twitch.tv/discord")
+            .details("Any%")
+            .details_url("https://www.speedrun.com")
+            .state_url("https://www.speedrun.com/leaderboards")
+            .status_display_type(StatusDisplayType::Details)
+            .buttons(vec![
+                Button::new("Watch", "https://twitch.tv/discord"),
+                Button::new("Leaderboard", "https://www.speedrun.com"),
+            ])
+            .call()
+            .unwrap();
+        let activity_request = serde_json::to_string(&activity_request).unwrap();
+        assert_eq!(
+            activity_request,
+            r##"{"name":"Speedrun","type":1,"url":"https://twitch.tv/discord","details":"Any%","details_url":"https://www.speedrun.com","state_url":"https://www.speedrun.com/leaderboards","status_display_type":2,"buttons":[{"label":"Watch","url":"https://twitch.tv/discord"},{"label":"Leaderboard","url":"https://www.speedrun.com"}]}"##
+        );
+    }
+
+    #[test]
+    fn validate_activity_request() {
+        let err = Activity::request_builder()
+            .activity_type(ActivityType::Playing)
+            .state("a")
+            .call()
+            .unwrap_err();
+        assert_eq!(
+            err,
+            ActivityValidationError::InvalidLength {
+                field: "state",
+                len: 1,
+                min: 2,
+                max: 128
+            }
+        );
+        let err = Activity::request_builder()
+            .activity_type(ActivityType::Playing)
+            .buttons(vec![Button::new("Join", "https://discord.com")])
+            .secrets(Secrets::builder().join("123").build())
+            .call()
+            .unwrap_err();
+        assert_eq!(err, ActivityValidationError::ButtonsWithSecrets);
+        let err = Activity::request_builder()
+            .activity_type(ActivityType::Playing)
+            .buttons(vec![Button::new("Join", "http://discord.com")])
+            .call()
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ActivityValidationError::InsecureButtonUrl { index: 0, .. }
+        ));
+        let err = Activity::request_builder()
+            .activity_type(ActivityType::Playing)
+            .party(Party::builder().size([5, 4]).build())
+            .call()
+            .unwrap_err();
+        assert_eq!(
+            err,
+            ActivityValidationError::PartySize { current: 5, max: 4 }
+        );
+        let err = Activity::request_builder()
+            .activity_type(ActivityType::Playing)
+            .timestamps((1_507_665_886, 1_507_666_886))
+            .call()
+            .unwrap_err();
+        assert_eq!(
+            err,
+            ActivityValidationError::TimestampNotMillis {
+                field: "timestamps.start",
+                value: 1_507_665_886
+            }
+        );
+    }
+
+    #[test]
+    fn skip_activity_request_validation() {
+        let activity_request = Activity::request_builder()
+            .activity_type(ActivityType::Playing)
+            .state("a")
+            .skip_validation(true)
+            .call()
+            .unwrap();
+        assert_eq!(activity_request.activity().state, Some("a".to_string()));
+        assert!(activity_request.validate().is_err());
+    }
+
+    #[test]
+    fn construct_timestamps() {
+        let elapsed = Timestamps::elapsed_since_now();
+        assert!(elapsed.start.unwrap() >= MIN_TIMESTAMP_MILLIS);
+        assert_eq!(elapsed.end, None);
+        let ends_in = Timestamps::ends_in(std::time::Duration::from_secs(5 * 60));
+        assert!(ends_in.end.unwrap() >= elapsed.start.unwrap() + 5 * 60 * 1000);
+        let activity_request = Activity::request_builder()
+            .activity_type(ActivityType::Playing)
+            .timestamps(ends_in)
+            .call();
+        assert!(activity_request.is_ok());
+    }
 }
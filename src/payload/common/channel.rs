@@ -22,6 +22,7 @@ pub struct ChannelResponse {
 pub struct Channel {
     pub id: Option<String>,
     pub name: Option<String>,
+    #[serde(rename = "type")]
     pub channel_type: Option<ChannelType>,
 }
 
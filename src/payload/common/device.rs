@@ -1,7 +1,8 @@
-use bon::{Builder, builder};
+use bon::Builder;
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
 use strum_macros::EnumString;
+use thiserror::Error;
 use uuid::Uuid;
 
 #[skip_serializing_none]
@@ -21,7 +22,7 @@ pub struct Device {
     })]
     pub related: Vec<Uuid>,
     #[builder(into)]
-    pub echo_collection: Option<bool>,
+    pub echo_cancellation: Option<bool>,
     #[builder(into)]
     pub noise_suppression: Option<bool>,
     #[builder(into)]
@@ -72,3 +73,11 @@ impl Model {
         }
     }
 }
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Error)]
+pub enum DeviceError {
+    #[error("Device {0} is listed more than once...")]
+    DuplicateId(Uuid),
+    #[error("Device {id} is related to device {related} which does not exist...")]
+    UnknownRelatedDevice { id: Uuid, related: Uuid },
+}
@@ -0,0 +1,115 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_repr::{Deserialize_repr, Serialize_repr};
+use strum_macros::EnumString;
+use thiserror::Error;
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct Entitlement {
+    pub id: Option<String>,
+    pub sku_id: Option<String>,
+    pub application_id: Option<String>,
+    pub user_id: Option<String>,
+    #[serde(rename = "type")]
+    pub entitlement_type: Option<EntitlementType>,
+    pub gift_code_flags: Option<u32>,
+    pub gifter_user_id: Option<String>,
+    pub starts_at: Option<DateTime<Utc>>,
+    pub ends_at: Option<DateTime<Utc>>,
+    pub consumed: Option<bool>,
+    pub deleted: Option<bool>,
+}
+
+impl Entitlement {
+    /// Whether the entitlement still grants access to its SKU at the given time
+    pub fn is_active_at(&self, now: DateTime<Utc>) -> bool {
+        self.deleted != Some(true)
+            && self.starts_at.is_none_or(|starts_at| starts_at <= now)
+            && self.ends_at.is_none_or(|ends_at| now < ends_at)
+    }
+}
+
+#[derive(Debug, Copy, Clone, Deserialize_repr, Serialize_repr, PartialEq, Eq, Hash, EnumString)]
+#[repr(u8)]
+pub enum EntitlementType {
+    Purchase = 1,
+    PremiumSubscription = 2,
+    DeveloperGift = 3,
+    TestModePurchase = 4,
+    FreePurchase = 5,
+    UserGift = 6,
+    PremiumPurchase = 7,
+    ApplicationSubscription = 8,
+}
+
+impl TryFrom<u8> for EntitlementType {
+    type Error = StoreError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(EntitlementType::Purchase),
+            2 => Ok(EntitlementType::PremiumSubscription),
+            3 => Ok(EntitlementType::DeveloperGift),
+            4 => Ok(EntitlementType::TestModePurchase),
+            5 => Ok(EntitlementType::FreePurchase),
+            6 => Ok(EntitlementType::UserGift),
+            7 => Ok(EntitlementType::PremiumPurchase),
+            8 => Ok(EntitlementType::ApplicationSubscription),
+            _ => Err(StoreError::InvalidEntitlementType(value)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct Sku {
+    pub id: Option<String>,
+    pub name: Option<String>,
+    #[serde(rename = "type")]
+    pub sku_type: Option<SkuType>,
+    pub price: Option<SkuPrice>,
+    pub application_id: Option<String>,
+    pub flags: Option<u32>,
+    pub release_date: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct SkuPrice {
+    /// Price in the smallest unit of the currency, e.g. cents
+    pub amount: Option<u64>,
+    pub currency: Option<String>,
+}
+
+#[derive(Debug, Copy, Clone, Deserialize_repr, Serialize_repr, PartialEq, Eq, Hash, EnumString)]
+#[repr(u8)]
+pub enum SkuType {
+    Application = 1,
+    Dlc = 2,
+    Consumable = 3,
+    Bundle = 4,
+    Subscription = 5,
+    SubscriptionGroup = 6,
+}
+
+impl TryFrom<u8> for SkuType {
+    type Error = StoreError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(SkuType::Application),
+            2 => Ok(SkuType::Dlc),
+            3 => Ok(SkuType::Consumable),
+            4 => Ok(SkuType::Bundle),
+            5 => Ok(SkuType::Subscription),
+            6 => Ok(SkuType::SubscriptionGroup),
+            _ => Err(StoreError::InvalidSkuType(value)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Error)]
+pub enum StoreError {
+    #[error("EntitlementType {0} does not exist...")]
+    InvalidEntitlementType(u8),
+    #[error("SkuType {0} does not exist...")]
+    InvalidSkuType(u8),
+}
@@ -0,0 +1,95 @@
+use std::collections::BTreeMap;
+
+use bon::Builder;
+use serde::{Deserialize, Serialize};
+use serde_repr::{Deserialize_repr, Serialize_repr};
+use strum_macros::EnumString;
+use thiserror::Error;
+
+use super::user::User;
+
+/// Metadata attached to a lobby or a lobby member
+pub type LobbyMetadata = BTreeMap<String, String>;
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct Lobby {
+    pub id: Option<String>,
+    #[serde(rename = "type")]
+    pub lobby_type: Option<LobbyType>,
+    pub owner_id: Option<String>,
+    pub secret: Option<String>,
+    pub capacity: Option<u32>,
+    pub locked: Option<bool>,
+    pub metadata: Option<LobbyMetadata>,
+    pub members: Option<Vec<LobbyMember>>,
+    pub application_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct LobbyMember {
+    pub user: Option<User>,
+    pub metadata: Option<LobbyMetadata>,
+}
+
+impl LobbyMember {
+    /// Returns the id of the user behind the member
+    pub fn user_id(&self) -> Option<&str> {
+        self.user.as_ref()?.id.as_deref()
+    }
+}
+
+#[derive(Debug, Copy, Clone, Deserialize_repr, Serialize_repr, PartialEq, Eq, Hash, EnumString)]
+#[repr(u8)]
+pub enum LobbyType {
+    Private = 1,
+    Public = 2,
+}
+
+impl TryFrom<u8> for LobbyType {
+    type Error = LobbyError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(LobbyType::Private),
+            2 => Ok(LobbyType::Public),
+            _ => Err(LobbyError::InvalidLobbyType(value)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Error)]
+pub enum LobbyError {
+    #[error("LobbyType {0} does not exist...")]
+    InvalidLobbyType(u8),
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash, Builder)]
+pub struct LobbyId {
+    #[builder(into)]
+    pub lobby_id: String,
+}
+
+impl<T: Into<String>> From<T> for LobbyId {
+    fn from(value: T) -> Self {
+        Self {
+            lobby_id: value.into(),
+        }
+    }
+}
+
+mod macros {
+    macro_rules! impl_lobby_id_type {
+        ($args_name: ident) => {
+            #[derive(Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq, Eq, Hash)]
+            pub struct $args_name(pub $crate::payload::common::lobby::LobbyId);
+            impl From<$crate::payload::common::lobby::LobbyId> for $args_name {
+                fn from(value: $crate::payload::common::lobby::LobbyId) -> Self {
+                    Self(value)
+                }
+            }
+        };
+    }
+    pub(crate) use impl_lobby_id_type;
+}
+
+pub(crate) use macros::impl_lobby_id_type;
@@ -4,12 +4,17 @@ pub mod attachment;
 pub mod channel;
 pub mod device;
 pub mod embed;
+pub mod entitlement;
 pub mod guild;
 pub mod level;
+pub mod lobby;
 pub mod message;
 pub mod oauth2;
 pub mod opcode;
 pub mod pan;
+pub mod permission;
+pub mod relationship;
+pub mod soundboard;
 pub mod team;
 pub mod user;
 pub mod voice;
@@ -1,4 +1,4 @@
-use bon::{Builder, builder};
+use bon::Builder;
 use ordered_float::OrderedFloat;
 use serde::{Deserialize, Serialize};
 
@@ -0,0 +1,86 @@
+use serde::{Deserialize, Serialize};
+use serde_repr::{Deserialize_repr, Serialize_repr};
+use strum_macros::EnumString;
+use thiserror::Error;
+
+use super::{activity::Activity, user::User};
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct Relationship {
+    #[serde(rename = "type")]
+    pub relationship_type: Option<RelationshipType>,
+    pub user: Option<User>,
+    pub presence: Option<Presence>,
+}
+
+impl Relationship {
+    /// Returns the activity of the related user if they are a friend and currently have one
+    pub fn friend_activity(&self) -> Option<&Activity> {
+        if self.relationship_type == Some(RelationshipType::Friend) {
+            self.presence.as_ref()?.activity.as_ref()
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Deserialize_repr, Serialize_repr, PartialEq, Eq, Hash, EnumString)]
+#[repr(u8)]
+pub enum RelationshipType {
+    None = 0,
+    Friend = 1,
+    Blocked = 2,
+    PendingIncoming = 3,
+    PendingOutgoing = 4,
+    Implicit = 5,
+}
+
+impl TryFrom<u8> for RelationshipType {
+    type Error = RelationshipError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(RelationshipType::None),
+            1 => Ok(RelationshipType::Friend),
+            2 => Ok(RelationshipType::Blocked),
+            3 => Ok(RelationshipType::PendingIncoming),
+            4 => Ok(RelationshipType::PendingOutgoing),
+            5 => Ok(RelationshipType::Implicit),
+            _ => Err(RelationshipError::InvalidRelationshipType(value)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct Presence {
+    pub status: Option<Status>,
+    pub activity: Option<Activity>,
+}
+
+#[derive(
+    Debug,
+    Copy,
+    Clone,
+    Deserialize,
+    Serialize,
+    PartialEq,
+    Eq,
+    Hash,
+    EnumString,
+    strum_macros::Display,
+)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum Status {
+    Online,
+    Idle,
+    Dnd,
+    Invisible,
+    Offline,
+}
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Error)]
+pub enum RelationshipError {
+    #[error("RelationshipType {0} does not exist...")]
+    InvalidRelationshipType(u8),
+}
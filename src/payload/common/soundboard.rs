@@ -0,0 +1,16 @@
+use ordered_float::OrderedFloat;
+use serde::{Deserialize, Serialize};
+
+use super::user::User;
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct SoundboardSound {
+    pub sound_id: Option<String>,
+    pub name: Option<String>,
+    pub volume: Option<OrderedFloat<f64>>,
+    pub emoji_id: Option<String>,
+    pub emoji_name: Option<String>,
+    pub guild_id: Option<String>,
+    pub available: Option<bool>,
+    pub user: Option<User>,
+}
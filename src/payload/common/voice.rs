@@ -1,4 +1,4 @@
-use bon::{Builder, builder};
+use bon::Builder;
 use chrono::{DateTime, Utc};
 use ordered_float::OrderedFloat;
 use serde::{Deserialize, Serialize};
@@ -86,6 +86,7 @@ pub struct VoiceSettingsOutput(pub VoiceSettingsIO);
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash, Builder)]
 pub struct VoiceSettingsMode {
+    #[serde(rename = "type")]
     #[builder(into)]
     pub mode_type: Option<ModeType>,
     #[builder(into)]
@@ -1,45 +1,170 @@
-#![cfg(feature = "untested")]
-use bon::{Builder, builder};
+use std::collections::HashSet;
+
+use bon::bon;
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
 
-use crate::payload::common::device::Device;
+use crate::payload::{
+    EmptyBracket,
+    common::device::{Device, DeviceError},
+};
 
 use super::macros::impl_request_args_type;
 
 #[skip_serializing_none]
-#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash, Builder)]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[serde(try_from = "UncheckedSetCertifiedDevicesArgs")]
 pub struct SetCertifiedDevicesArgs {
-    #[builder(with = |devices: impl IntoIterator<Item = Device>| {
-        devices.into_iter().collect()
-    })]
     devices: Vec<Device>,
 }
 
-#[cfg(feature = "untested")]
+/// [SetCertifiedDevicesArgs] before the devices are validated
+#[derive(Deserialize)]
+struct UncheckedSetCertifiedDevicesArgs {
+    devices: Vec<Device>,
+}
+
+impl TryFrom<UncheckedSetCertifiedDevicesArgs> for SetCertifiedDevicesArgs {
+    type Error = DeviceError;
+
+    fn try_from(args: UncheckedSetCertifiedDevicesArgs) -> Result<Self, Self::Error> {
+        Self::builder().devices(args.devices).build()
+    }
+}
+
+#[bon]
+impl SetCertifiedDevicesArgs {
+    /// Constructs the arguments for the `SET_CERTIFIED_DEVICES` command
+    ///
+    /// # Errors
+    /// A [DeviceError] is returned if two devices share the same id or if a device is related to
+    /// a device that is not part of `devices`
+    #[builder]
+    pub fn new(
+        #[builder(with = |devices: impl IntoIterator<Item = Device>| {
+            devices.into_iter().collect()
+        })]
+        devices: Vec<Device>,
+    ) -> Result<Self, DeviceError> {
+        let mut ids = HashSet::with_capacity(devices.len());
+        for device in devices.iter() {
+            if !ids.insert(device.id) {
+                return Err(DeviceError::DuplicateId(device.id));
+            }
+        }
+        for device in devices.iter() {
+            if let Some(related) = device.related.iter().find(|related| !ids.contains(related)) {
+                return Err(DeviceError::UnknownRelatedDevice {
+                    id: device.id,
+                    related: *related,
+                });
+            }
+        }
+        Ok(Self { devices })
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct SetCertifiedDevicesData(pub Option<EmptyBracket>);
+
 impl_request_args_type!(SetCertifiedDevices);
 
 #[cfg(test)]
 mod tests {
+    use pretty_assertions::assert_eq;
+    use serde_json::Value;
     use uuid::Uuid;
 
-    use crate::payload::common::device::{Device, DeviceType, Model, Vendor};
+    use crate::payload::common::device::{Device, DeviceError, DeviceType, Model, Vendor};
 
-    use super::SetCertifiedDevicesArgs;
+    use super::{SetCertifiedDevicesArgs, SetCertifiedDevicesData};
 
-    #[test]
-    fn construct_certified_devices() {
-        let device = Device::builder()
+    fn device(id: Uuid, related: impl IntoIterator<Item = Uuid>) -> Device {
+        Device::builder()
             .device_type(DeviceType::AudioInput)
-            .id(Uuid::new_v4())
-            .vendor(Vendor::new("123", "43"))
-            .model(Model::new("123", "455"))
-            .related([Uuid::new_v4(), Uuid::new_v4()])
-            .echo_collection(true)
+            .id(id)
+            .vendor(Vendor::new("SteelSeries", "https://steelseries.com"))
+            .model(Model::new(
+                "Arctis 7",
+                "https://steelseries.com/gaming-headsets/arctis-7",
+            ))
+            .related(related)
+            .echo_cancellation(true)
             .noise_suppression(true)
-            .build();
-        let scda = SetCertifiedDevicesArgs::builder().devices([device]).build();
+            .automatic_gain_control(true)
+            .hardware_mute(false)
+            .build()
+    }
+
+    #[test]
+    fn construct_certified_devices() {
+        let input = Uuid::new_v4();
+        let output = Uuid::new_v4();
+        let scda = SetCertifiedDevicesArgs::builder()
+            .devices([device(input, [output]), device(output, [input])])
+            .build()
+            .unwrap();
         let json = serde_json::to_string(&scda).unwrap();
-        assert!(json.contains("\"echo_collection\":true"));
+        assert!(json.contains("\"echo_cancellation\":true"));
+    }
+
+    #[test]
+    fn construct_certified_devices_duplicate_id() {
+        let id = Uuid::new_v4();
+        let err = SetCertifiedDevicesArgs::builder()
+            .devices([device(id, []), device(id, [])])
+            .build()
+            .unwrap_err();
+        assert_eq!(err, DeviceError::DuplicateId(id));
+    }
+
+    #[test]
+    fn construct_certified_devices_unknown_related() {
+        let id = Uuid::new_v4();
+        let related = Uuid::new_v4();
+        let err = SetCertifiedDevicesArgs::builder()
+            .devices([device(id, [related])])
+            .build()
+            .unwrap_err();
+        assert_eq!(err, DeviceError::UnknownRelatedDevice { id, related });
+    }
+
+    #[test]
+    fn deserialize_certified_devices_validates() {
+        let id = Uuid::new_v4();
+        let related = Uuid::new_v4();
+        let duplicate = serde_json::json!({"devices": [device(id, []), device(id, [])]});
+        let err = serde_json::from_value::<SetCertifiedDevicesArgs>(duplicate).unwrap_err();
+        assert_eq!(err.to_string(), DeviceError::DuplicateId(id).to_string());
+        let unknown = serde_json::json!({"devices": [device(id, [related])]});
+        let err = serde_json::from_value::<SetCertifiedDevicesArgs>(unknown).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            DeviceError::UnknownRelatedDevice { id, related }.to_string()
+        );
+    }
+
+    #[test]
+    fn serialize_certified_devices_matches_captured() {
+        let payload = r##"{"devices":[{"type":"audioinput","id":"aafc2003-44ec-4ed3-9ec5-95c8ac3fcc3b","vendor":{"name":"SteelSeries","url":"https://steelseries.com"},"model":{"name":"Arctis 7","url":"https://steelseries.com/gaming-headsets/arctis-7"},"related":["aafc2003-44ec-4ed3-9ec5-95c8ac3fcc3b"],"echo_cancellation":true,"noise_suppression":true,"automatic_gain_control":true,"hardware_mute":false}]}"##;
+        let id = Uuid::parse_str("aafc2003-44ec-4ed3-9ec5-95c8ac3fcc3b").unwrap();
+        let scda = SetCertifiedDevicesArgs::builder()
+            .devices([device(id, [id])])
+            .build()
+            .unwrap();
+        assert_eq!(
+            serde_json::to_value(&scda).unwrap(),
+            serde_json::from_str::<Value>(payload).unwrap()
+        );
+        assert_eq!(
+            serde_json::from_str::<SetCertifiedDevicesArgs>(payload).unwrap(),
+            scda
+        );
+    }
+
+    #[test]
+    fn deserialize_certified_devices_data() {
+        let data = serde_json::from_str::<SetCertifiedDevicesData>("null").unwrap();
+        assert_eq!(data, SetCertifiedDevicesData(None));
     }
 }
@@ -0,0 +1,149 @@
+//! Commands and events used by [Activities] embedded in the Discord client.
+//!
+//! [Activities]: https://discord.com/developers/docs/activities/overview
+use bon::Builder;
+use serde::{Deserialize, Serialize};
+use serde_repr::{Deserialize_repr, Serialize_repr};
+use serde_with::skip_serializing_none;
+use url::Url;
+
+use super::{
+    EmptyBracket,
+    common::user::User,
+    macros::{impl_empty_args_type, impl_event_args_type, impl_request_args_type},
+};
+
+/// A user connected to the same Activity instance
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct Participant {
+    #[serde(flatten)]
+    pub user: User,
+    pub nickname: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct GetActivityInstanceConnectedParticipantsData {
+    pub participants: Vec<Participant>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash, Builder)]
+pub struct SetOrientationLockStateArgs {
+    lock_state: OrientationLockState,
+    picture_in_picture_lock_state: Option<OrientationLockState>,
+    grid_lock_state: Option<OrientationLockState>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct SetOrientationLockStateData(pub Option<EmptyBracket>);
+
+#[derive(Debug, Copy, Clone, Deserialize_repr, Serialize_repr, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum OrientationLockState {
+    Unlocked = 1,
+    Portrait = 2,
+    Landscape = 3,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct GetPlatformBehaviorsData {
+    #[serde(rename = "iosKeyboardResizesView")]
+    pub ios_keyboard_resizes_view: Option<bool>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct UserSettingsGetLocaleData {
+    pub locale: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash, Builder)]
+pub struct OpenShareMomentDialogArgs {
+    #[serde(rename = "mediaUrl")]
+    media_url: Url,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct OpenShareMomentDialogData(pub Option<EmptyBracket>);
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct InitiateImageUploadData {
+    pub image_url: Option<Url>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct ActivityInstanceParticipantsUpdateData {
+    pub participants: Vec<Participant>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct CurrentUserUpdateData(pub User);
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct OrientationUpdateData {
+    pub screen_orientation: Option<ScreenOrientation>,
+}
+
+#[derive(Debug, Copy, Clone, Deserialize_repr, Serialize_repr, PartialEq, Eq, Hash)]
+#[repr(i8)]
+pub enum ScreenOrientation {
+    Unhandled = -1,
+    Portrait = 0,
+    Landscape = 1,
+}
+
+impl_empty_args_type!(GetActivityInstanceConnectedParticipants);
+impl_empty_args_type!(GetPlatformBehaviors);
+impl_empty_args_type!(UserSettingsGetLocale);
+impl_empty_args_type!(InitiateImageUpload);
+impl_empty_args_type!(ActivityInstanceParticipantsUpdate);
+impl_empty_args_type!(CurrentUserUpdate);
+impl_empty_args_type!(OrientationUpdate);
+
+impl_request_args_type!(GetActivityInstanceConnectedParticipants);
+impl_request_args_type!(SetOrientationLockState);
+impl_request_args_type!(GetPlatformBehaviors);
+impl_request_args_type!(UserSettingsGetLocale);
+impl_request_args_type!(OpenShareMomentDialog);
+impl_request_args_type!(InitiateImageUpload);
+
+impl_event_args_type!(ActivityInstanceParticipantsUpdate);
+impl_event_args_type!(CurrentUserUpdate);
+impl_event_args_type!(OrientationUpdate);
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::{
+        GetActivityInstanceConnectedParticipantsData, OrientationLockState, OrientationUpdateData,
+        ScreenOrientation, SetOrientationLockStateArgs,
+    };
+
+    #[test]
+    fn deserialize_connected_participants() {
+        let payload = r##"{"participants":[{"id":"53908232506183680","username":"Mason","discriminator":"0","avatar":null,"global_name":"Mason","bot":false,"flags":0,"nickname":"mason"}]}"##;
+        let data =
+            serde_json::from_str::<GetActivityInstanceConnectedParticipantsData>(payload).unwrap();
+        assert_eq!(
+            data.participants[0].user.id,
+            Some("53908232506183680".to_string())
+        );
+        assert_eq!(data.participants[0].nickname, Some("mason".to_string()));
+    }
+
+    #[test]
+    fn serialize_set_orientation_lock_state() {
+        let args = SetOrientationLockStateArgs::builder()
+            .lock_state(OrientationLockState::Landscape)
+            .build();
+        let args = serde_json::to_string(&args).unwrap();
+        assert_eq!(args, r##"{"lock_state":3}"##);
+    }
+
+    #[test]
+    fn deserialize_orientation_update() {
+        let payload = r##"{"screen_orientation":1,"orientation":"landscape"}"##;
+        let data = serde_json::from_str::<OrientationUpdateData>(payload).unwrap();
+        assert_eq!(data.screen_orientation, Some(ScreenOrientation::Landscape));
+    }
+}
@@ -2,6 +2,246 @@ use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
 pub struct ErrorData {
-    pub code: Option<u32>,
+    pub code: Option<RpcErrorCode>,
     pub message: Option<String>,
 }
+
+/// Data of the CLOSE frame sent by Discord right before it closes the IPC connection
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct CloseData {
+    pub code: Option<RpcCloseCode>,
+    pub message: Option<String>,
+}
+
+/// RPC error code sent back in the `data` of an `ERROR` event
+///
+/// Codes that are not documented by Discord are kept in [RpcErrorCode::Other] so that a new code
+/// never fails deserialization of the response.
+///
+/// See the [RPC Error Codes] documentation.
+///
+/// [RPC Error Codes]: https://discord.com/developers/docs/topics/opcodes-and-status-codes#rpc-rpc-error-codes
+#[derive(Debug, Copy, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[serde(from = "u32", into = "u32")]
+pub enum RpcErrorCode {
+    /// An unknown error occurred
+    UnknownError,
+    /// You sent an invalid payload
+    InvalidPayload,
+    /// Invalid command name specified
+    InvalidCommand,
+    /// Invalid guild ID specified
+    InvalidGuild,
+    /// Invalid event name specified
+    InvalidEvent,
+    /// Invalid channel ID specified
+    InvalidChannel,
+    /// The client is not authenticated or the application lacks the scope for the command
+    NotAuthenticated,
+    /// An invalid OAuth2 application ID was used to authorize
+    InvalidClientId,
+    /// An invalid OAuth2 application origin was used to authorize
+    InvalidOrigin,
+    /// An invalid OAuth2 token was used to authorize or authenticate
+    InvalidToken,
+    /// The specified user ID was invalid
+    InvalidUser,
+    /// A standard OAuth2 error occurred, e.g. the user denied the authorization
+    OAuth2Error,
+    /// An asynchronous `SELECT_TEXT_CHANNEL`/`SELECT_VOICE_CHANNEL` command timed out
+    SelectChannelTimedOut,
+    /// An asynchronous `GET_GUILD` command timed out
+    GetGuildTimedOut,
+    /// You tried to join a user to a voice channel but the user was already in one
+    SelectVoiceForceRequired,
+    /// You tried to capture more than one shortcut key at once
+    CaptureShortcutAlreadyListening,
+    /// A code that is not documented by Discord
+    Other(u32),
+}
+
+impl RpcErrorCode {
+    /// Whether sending the same request again may succeed
+    pub const fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            RpcErrorCode::UnknownError
+                | RpcErrorCode::SelectChannelTimedOut
+                | RpcErrorCode::GetGuildTimedOut
+        )
+    }
+
+    /// Whether the user has to go through the OAuth2 authorization flow again before the request
+    /// can succeed
+    pub const fn requires_reauth(&self) -> bool {
+        matches!(
+            self,
+            RpcErrorCode::NotAuthenticated | RpcErrorCode::InvalidToken | RpcErrorCode::OAuth2Error
+        )
+    }
+}
+
+impl From<u32> for RpcErrorCode {
+    fn from(value: u32) -> Self {
+        match value {
+            1000 => RpcErrorCode::UnknownError,
+            4000 => RpcErrorCode::InvalidPayload,
+            4002 => RpcErrorCode::InvalidCommand,
+            4003 => RpcErrorCode::InvalidGuild,
+            4004 => RpcErrorCode::InvalidEvent,
+            4005 => RpcErrorCode::InvalidChannel,
+            4006 => RpcErrorCode::NotAuthenticated,
+            4007 => RpcErrorCode::InvalidClientId,
+            4008 => RpcErrorCode::InvalidOrigin,
+            4009 => RpcErrorCode::InvalidToken,
+            4010 => RpcErrorCode::InvalidUser,
+            5000 => RpcErrorCode::OAuth2Error,
+            5001 => RpcErrorCode::SelectChannelTimedOut,
+            5002 => RpcErrorCode::GetGuildTimedOut,
+            5003 => RpcErrorCode::SelectVoiceForceRequired,
+            5004 => RpcErrorCode::CaptureShortcutAlreadyListening,
+            other => RpcErrorCode::Other(other),
+        }
+    }
+}
+
+impl From<RpcErrorCode> for u32 {
+    fn from(value: RpcErrorCode) -> Self {
+        match value {
+            RpcErrorCode::UnknownError => 1000,
+            RpcErrorCode::InvalidPayload => 4000,
+            RpcErrorCode::InvalidCommand => 4002,
+            RpcErrorCode::InvalidGuild => 4003,
+            RpcErrorCode::InvalidEvent => 4004,
+            RpcErrorCode::InvalidChannel => 4005,
+            RpcErrorCode::NotAuthenticated => 4006,
+            RpcErrorCode::InvalidClientId => 4007,
+            RpcErrorCode::InvalidOrigin => 4008,
+            RpcErrorCode::InvalidToken => 4009,
+            RpcErrorCode::InvalidUser => 4010,
+            RpcErrorCode::OAuth2Error => 5000,
+            RpcErrorCode::SelectChannelTimedOut => 5001,
+            RpcErrorCode::GetGuildTimedOut => 5002,
+            RpcErrorCode::SelectVoiceForceRequired => 5003,
+            RpcErrorCode::CaptureShortcutAlreadyListening => 5004,
+            RpcErrorCode::Other(other) => other,
+        }
+    }
+}
+
+/// RPC close code sent by Discord when it closes the IPC connection
+///
+/// Requests made after the connection was closed fail with
+/// [SdkClientError::ConnectionClosed][crate::client::SdkClientError::ConnectionClosed].
+///
+/// See the [RPC Close Event Codes] documentation.
+///
+/// [RPC Close Event Codes]: https://discord.com/developers/docs/topics/opcodes-and-status-codes#rpc-rpc-close-event-codes
+#[derive(Debug, Copy, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[serde(from = "u32", into = "u32")]
+pub enum RpcCloseCode {
+    /// You connected to the RPC server with an invalid client ID
+    InvalidClientId,
+    /// You connected to the RPC server with an invalid origin
+    InvalidOrigin,
+    /// You are being rate limited
+    RateLimited,
+    /// The OAuth2 token associated with a connection was revoked
+    TokenRevoked,
+    /// The RPC Server version specified in the connection string was not valid
+    InvalidVersion,
+    /// The encoding specified in the connection string was not valid
+    InvalidEncoding,
+    /// A code that is not documented by Discord
+    Other(u32),
+}
+
+impl RpcCloseCode {
+    /// Whether reconnecting with the same configuration may succeed
+    pub const fn is_retryable(&self) -> bool {
+        matches!(self, RpcCloseCode::RateLimited)
+    }
+
+    /// Whether the user has to go through the OAuth2 authorization flow again after reconnecting
+    pub const fn requires_reauth(&self) -> bool {
+        matches!(self, RpcCloseCode::TokenRevoked)
+    }
+}
+
+impl From<u32> for RpcCloseCode {
+    fn from(value: u32) -> Self {
+        match value {
+            4000 => RpcCloseCode::InvalidClientId,
+            4001 => RpcCloseCode::InvalidOrigin,
+            4002 => RpcCloseCode::RateLimited,
+            4003 => RpcCloseCode::TokenRevoked,
+            4004 => RpcCloseCode::InvalidVersion,
+            4005 => RpcCloseCode::InvalidEncoding,
+            other => RpcCloseCode::Other(other),
+        }
+    }
+}
+
+impl From<RpcCloseCode> for u32 {
+    fn from(value: RpcCloseCode) -> Self {
+        match value {
+            RpcCloseCode::InvalidClientId => 4000,
+            RpcCloseCode::InvalidOrigin => 4001,
+            RpcCloseCode::RateLimited => 4002,
+            RpcCloseCode::TokenRevoked => 4003,
+            RpcCloseCode::InvalidVersion => 4004,
+            RpcCloseCode::InvalidEncoding => 4005,
+            RpcCloseCode::Other(other) => other,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::{CloseData, ErrorData, RpcCloseCode, RpcErrorCode};
+
+    #[test]
+    fn deserialize_error_data() {
+        let payload = r##"{"code":4005,"message":"Invalid channel id: 1234"}"##;
+        let data = serde_json::from_str::<ErrorData>(payload).unwrap();
+        assert_eq!(data.code, Some(RpcErrorCode::InvalidChannel));
+        assert!(!data.code.unwrap().requires_reauth());
+    }
+
+    #[test]
+    fn deserialize_error_data_oauth2() {
+        let payload = r##"{"code":5000,"message":"OAuth2 Error: access_denied: The resource owner or authorization server denied the request"}"##;
+        let data = serde_json::from_str::<ErrorData>(payload).unwrap();
+        assert_eq!(data.code, Some(RpcErrorCode::OAuth2Error));
+        assert!(data.code.unwrap().requires_reauth());
+        assert!(!data.code.unwrap().is_retryable());
+    }
+
+    #[test]
+    fn deserialize_error_data_undocumented_code() {
+        let payload = r##"{"code":4011,"message":"Invalid invite id"}"##;
+        let data = serde_json::from_str::<ErrorData>(payload).unwrap();
+        assert_eq!(data.code, Some(RpcErrorCode::Other(4011)));
+        let data = serde_json::to_string(&data).unwrap();
+        assert!(data.contains("\"code\":4011"));
+    }
+
+    #[test]
+    fn deserialize_close_data() {
+        let payload = r##"{"code":4000,"message":"Invalid Client ID"}"##;
+        let data = serde_json::from_str::<CloseData>(payload).unwrap();
+        assert_eq!(data.code, Some(RpcCloseCode::InvalidClientId));
+        assert_eq!(data.message.as_deref(), Some("Invalid Client ID"));
+    }
+
+    #[test]
+    fn close_code_round_trip() {
+        for code in 4000..=4005 {
+            assert_eq!(u32::from(RpcCloseCode::from(code)), code);
+        }
+        assert!(RpcCloseCode::from(4002).is_retryable());
+        assert!(RpcCloseCode::from(4003).requires_reauth());
+    }
+}
@@ -1,4 +1,4 @@
-use bon::{Builder, builder};
+use bon::Builder;
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
 use url::Url;
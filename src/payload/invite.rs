@@ -0,0 +1,125 @@
+use bon::Builder;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+use super::{EmptyBracket, macros::impl_request_args_type};
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash, Builder)]
+pub struct InviteBrowserArgs {
+    /// Invite code, e.g. `discord-developers` for `discord.gg/discord-developers`
+    #[builder(into)]
+    code: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct InviteBrowserData(pub Option<OpenedInvite>);
+
+/// The invite opened by an [InviteBrowserArgs] request
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct OpenedInvite {
+    pub code: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash, Builder)]
+pub struct GuildTemplateBrowserArgs {
+    /// Template code, e.g. `hgM48av5Q69A` for `discord.new/hgM48av5Q69A`
+    #[builder(into)]
+    code: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct GuildTemplateBrowserData(pub Option<EmptyBracket>);
+
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash, Builder)]
+pub struct DeepLinkArgs {
+    #[serde(rename = "type")]
+    link_type: DeepLinkType,
+    params: Option<DeepLinkParams>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct DeepLinkData(pub Option<EmptyBracket>);
+
+/// The page of the Discord client a [DeepLinkArgs] navigates to
+#[derive(Debug, Copy, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum DeepLinkType {
+    UserSettings,
+    Changelog,
+    Library,
+    StoreHome,
+    StoreListing,
+    PickGuildSettings,
+    Channel,
+    QuestHome,
+    DiscoveryGameResults,
+    Oauth2,
+    Shop,
+    Features,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash, Builder)]
+#[serde(rename_all = "camelCase")]
+pub struct DeepLinkParams {
+    #[builder(into)]
+    guild_id: Option<String>,
+    #[builder(into)]
+    channel_id: Option<String>,
+    #[builder(into)]
+    message_id: Option<String>,
+}
+
+impl_request_args_type!(InviteBrowser);
+impl_request_args_type!(GuildTemplateBrowser);
+impl_request_args_type!(DeepLink);
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::{
+        DeepLinkArgs, DeepLinkParams, DeepLinkType, InviteBrowserArgs, InviteBrowserData,
+        OpenedInvite,
+    };
+
+    #[test]
+    fn serialize_invite_browser() {
+        let args = InviteBrowserArgs::builder()
+            .code("discord-developers")
+            .build();
+        let args = serde_json::to_string(&args).unwrap();
+        assert_eq!(args, r##"{"code":"discord-developers"}"##);
+        let data = serde_json::from_str::<InviteBrowserData>(r##"{"code":"discord-developers"}"##)
+            .unwrap();
+        assert_eq!(
+            data,
+            InviteBrowserData(Some(OpenedInvite {
+                code: Some("discord-developers".to_string())
+            }))
+        );
+        let data = serde_json::from_str::<InviteBrowserData>("null").unwrap();
+        assert_eq!(data, InviteBrowserData(None));
+    }
+
+    #[test]
+    fn serialize_deep_link() {
+        let args = DeepLinkArgs::builder()
+            .link_type(DeepLinkType::Channel)
+            .params(
+                DeepLinkParams::builder()
+                    .guild_id("199737254929760256")
+                    .channel_id("199737254929760257")
+                    .message_id("199743874640379904")
+                    .build(),
+            )
+            .build();
+        let args = serde_json::to_string(&args).unwrap();
+        assert_eq!(
+            args,
+            r##"{"type":"CHANNEL","params":{"guildId":"199737254929760256","channelId":"199737254929760257","messageId":"199743874640379904"}}"##
+        );
+    }
+}
@@ -0,0 +1,193 @@
+use bon::Builder;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+use super::{
+    EmptyBracket,
+    common::lobby::{Lobby, LobbyMember, LobbyMetadata, LobbyType, impl_lobby_id_type},
+    macros::{impl_event_args_type, impl_request_args_type},
+};
+
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash, Builder)]
+pub struct CreateLobbyArgs {
+    #[serde(rename = "type")]
+    lobby_type: LobbyType,
+    capacity: u32,
+    locked: Option<bool>,
+    metadata: Option<LobbyMetadata>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct CreateLobbyData(pub Lobby);
+
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash, Builder)]
+pub struct UpdateLobbyArgs {
+    #[builder(into)]
+    id: String,
+    #[serde(rename = "type")]
+    lobby_type: Option<LobbyType>,
+    #[builder(into)]
+    owner_id: Option<String>,
+    capacity: Option<u32>,
+    locked: Option<bool>,
+    metadata: Option<LobbyMetadata>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct UpdateLobbyData(pub Option<EmptyBracket>);
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash, Builder)]
+pub struct DeleteLobbyArgs {
+    #[builder(into)]
+    id: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct DeleteLobbyData(pub Option<EmptyBracket>);
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash, Builder)]
+pub struct ConnectToLobbyArgs {
+    #[builder(into)]
+    id: String,
+    #[builder(into)]
+    secret: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct ConnectToLobbyData(pub Lobby);
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash, Builder)]
+pub struct DisconnectFromLobbyArgs {
+    #[builder(into)]
+    id: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct DisconnectFromLobbyData(pub Option<EmptyBracket>);
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash, Builder)]
+pub struct SendToLobbyArgs {
+    #[builder(into)]
+    lobby_id: String,
+    #[builder(into)]
+    data: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct SendToLobbyData(pub Option<EmptyBracket>);
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash, Builder)]
+pub struct UpdateLobbyMemberArgs {
+    #[builder(into)]
+    lobby_id: String,
+    #[builder(into)]
+    user_id: String,
+    metadata: LobbyMetadata,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct UpdateLobbyMemberData(pub Option<EmptyBracket>);
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct LobbyUpdateData(pub Lobby);
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct LobbyDeleteData {
+    pub id: Option<String>,
+    pub reason: Option<u32>,
+}
+
+/// Data sent when a member connects to, updates their metadata in or disconnects from a lobby
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct LobbyMemberEvent {
+    pub lobby_id: Option<String>,
+    pub member: Option<LobbyMember>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct LobbyMemberConnectData(pub LobbyMemberEvent);
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct LobbyMemberUpdateData(pub LobbyMemberEvent);
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct LobbyMemberDisconnectData(pub LobbyMemberEvent);
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct LobbyMessageData {
+    pub lobby_id: Option<String>,
+    pub sender_id: Option<String>,
+    pub data: Option<String>,
+}
+
+impl_lobby_id_type!(LobbyUpdateArgs);
+impl_lobby_id_type!(LobbyDeleteArgs);
+impl_lobby_id_type!(LobbyMemberConnectArgs);
+impl_lobby_id_type!(LobbyMemberUpdateArgs);
+impl_lobby_id_type!(LobbyMemberDisconnectArgs);
+impl_lobby_id_type!(LobbyMessageArgs);
+
+impl_request_args_type!(CreateLobby);
+impl_request_args_type!(UpdateLobby);
+impl_request_args_type!(DeleteLobby);
+impl_request_args_type!(ConnectToLobby);
+impl_request_args_type!(DisconnectFromLobby);
+impl_request_args_type!(SendToLobby);
+impl_request_args_type!(UpdateLobbyMember);
+
+impl_event_args_type!(LobbyUpdate);
+impl_event_args_type!(LobbyDelete);
+impl_event_args_type!(LobbyMemberConnect);
+impl_event_args_type!(LobbyMemberUpdate);
+impl_event_args_type!(LobbyMemberDisconnect);
+impl_event_args_type!(LobbyMessage);
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use crate::payload::common::lobby::{LobbyMetadata, LobbyType};
+
+    use super::{CreateLobbyArgs, CreateLobbyData, LobbyMemberConnectData, LobbyMessageData};
+
+    #[test]
+    fn serialize_create_lobby() {
+        let args = CreateLobbyArgs::builder()
+            .lobby_type(LobbyType::Private)
+            .capacity(4)
+            .metadata(LobbyMetadata::from([(
+                "map".to_string(),
+                "dust2".to_string(),
+            )]))
+            .build();
+        let args = serde_json::to_string(&args).unwrap();
+        assert_eq!(
+            args,
+            r##"{"type":1,"capacity":4,"metadata":{"map":"dust2"}}"##
+        );
+    }
+
+    #[test]
+    fn deserialize_create_lobby() {
+        let payload = r##"{"application_id":"310270644849737729","capacity":4,"id":"580087718095519744","locked":false,"members":[{"metadata":{},"user":{"avatar":null,"discriminator":"0","id":"53908232506183680","username":"Mason"}}],"metadata":{"map":"dust2"},"owner_id":"53908232506183680","secret":"a4ec5c2d0c3fb1f6","type":1}"##;
+        let data = serde_json::from_str::<CreateLobbyData>(payload).unwrap();
+        assert_eq!(data.0.id, Some("580087718095519744".to_string()));
+        assert_eq!(data.0.lobby_type, Some(LobbyType::Private));
+        assert_eq!(
+            data.0.members.unwrap()[0].user_id(),
+            Some("53908232506183680")
+        );
+    }
+
+    #[test]
+    fn deserialize_lobby_events() {
+        let payload = r##"{"lobby_id":"580087718095519744","member":{"metadata":{"team":"red"},"user":{"avatar":null,"discriminator":"0","id":"158284148040138752","username":"day2"}}}"##;
+        let data = serde_json::from_str::<LobbyMemberConnectData>(payload).unwrap();
+        assert_eq!(data.0.member.unwrap().user_id(), Some("158284148040138752"));
+        let payload = r##"{"lobby_id":"580087718095519744","sender_id":"158284148040138752","data":"hello"}"##;
+        let data = serde_json::from_str::<LobbyMessageData>(payload).unwrap();
+        assert_eq!(data.data, Some("hello".to_string()));
+    }
+}
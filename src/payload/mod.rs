@@ -9,6 +9,8 @@ use serde_with::skip_serializing_none;
 use strum_macros::EnumString;
 use uuid::Uuid;
 
+use crate::client::SdkClientError;
+
 /// Payload that is sent/received to/from the Discord IPC server
 #[skip_serializing_none]
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
@@ -58,13 +60,48 @@ pub enum Data {
     Unsubscribe(Box<UnsubscribeData>),
     GetVoiceSettings(Box<GetVoiceSettingsData>),
     SetVoiceSettings(Box<SetVoiceSettingsData>),
-    // #[cfg(feature = "untested")]
-    // SetCertifiedDevices(SetCertifiedDevicesArgs),
+    SetCertifiedDevices(Box<SetCertifiedDevicesData>),
     SetActivity(Box<SetActivityData>),
-    // #[cfg(feature = "untested")]
-    // SendActivityJoinInvite(SendActivityJoinInviteArgs),
-    // #[cfg(feature = "untested")]
-    // CloseActivityRequest(CloseActivityRequestArgs),
+    CaptureShortcut(Box<CaptureShortcutData>),
+    GetRelationships(Box<GetRelationshipsData>),
+    ToggleVideo(Box<ToggleVideoData>),
+    ToggleScreenshare(Box<ToggleScreenshareData>),
+    SetConfig(Box<SetConfigData>),
+    GetSoundboardSounds(Box<GetSoundboardSoundsData>),
+    PlaySoundboardSound(Box<PlaySoundboardSoundData>),
+    SetOverlayLocked(Box<SetOverlayLockedData>),
+    OpenOverlayActivityInvite(Box<OpenOverlayActivityInviteData>),
+    OpenOverlayGuildInvite(Box<OpenOverlayGuildInviteData>),
+    OpenOverlayVoiceSettings(Box<OpenOverlayVoiceSettingsData>),
+    InviteBrowser(Box<InviteBrowserData>),
+    GuildTemplateBrowser(Box<GuildTemplateBrowserData>),
+    DeepLink(Box<DeepLinkData>),
+    GetEntitlements(Box<GetEntitlementsData>),
+    GetSkus(Box<GetSkusData>),
+    StartPurchase(Box<StartPurchaseData>),
+    CreateLobby(Box<CreateLobbyData>),
+    UpdateLobby(Box<UpdateLobbyData>),
+    DeleteLobby(Box<DeleteLobbyData>),
+    ConnectToLobby(Box<ConnectToLobbyData>),
+    DisconnectFromLobby(Box<DisconnectFromLobbyData>),
+    SendToLobby(Box<SendToLobbyData>),
+    UpdateLobbyMember(Box<UpdateLobbyMemberData>),
+    GetUser(Box<GetUserData>),
+    GetChannelPermissions(Box<GetChannelPermissionsData>),
+    #[cfg(feature = "embedded-activity")]
+    GetActivityInstanceConnectedParticipants(Box<GetActivityInstanceConnectedParticipantsData>),
+    #[cfg(feature = "embedded-activity")]
+    SetOrientationLockState(Box<SetOrientationLockStateData>),
+    #[cfg(feature = "embedded-activity")]
+    GetPlatformBehaviors(Box<GetPlatformBehaviorsData>),
+    #[cfg(feature = "embedded-activity")]
+    UserSettingsGetLocale(Box<UserSettingsGetLocaleData>),
+    #[cfg(feature = "embedded-activity")]
+    OpenShareMomentDialog(Box<OpenShareMomentDialogData>),
+    #[cfg(feature = "embedded-activity")]
+    InitiateImageUpload(Box<InitiateImageUploadData>),
+    SendActivityJoinInvite(Box<SendActivityJoinInviteData>),
+    CloseActivityRequest(Box<CloseActivityRequestData>),
     GuildStatus(Box<GuildStatusData>),
     GuildCreate(Box<GuildCreateData>),
     ChannelCreate(Box<ChannelCreateData>),
@@ -72,21 +109,38 @@ pub enum Data {
     VoiceStateCreate(Box<VoiceStateCreateData>),
     VoiceStateUpdate(Box<VoiceStateUpdateData>),
     VoiceStateDelete(Box<VoiceStateDeleteData>),
-    // #[cfg(feature = "untested")]
-    // VoiceSettingsUpdate(VoiceSettingsUpdateArgs),
+    VoiceSettingsUpdate(Box<VoiceSettingsUpdateData>),
     VoiceConnectionStatus(Box<VoiceConnectionStatusData>),
     SpeakingStart(Box<SpeakingStartData>),
     SpeakingStop(Box<SpeakingStopData>),
     MessageCreate(Box<MessageCreateData>),
     MessageUpdate(Box<MessageUpdateData>),
     MessageDelete(Box<MessageDeleteData>),
-    // NotificationCreate(NotificationCreateArgs),
-    // #[cfg(feature = "untested")]
-    // ActivityJoin(ActivityJoinArgs),
-    // #[cfg(feature = "untested")]
-    // ActivitySpectate(ActivitySpectateArgs),
-    // #[cfg(feature = "untested")]
-    // ActivityJoinRequest(ActivityJoinRequestArgs),
+    NotificationCreate(Box<NotificationCreateData>),
+    ActivityJoin(Box<ActivityJoinData>),
+    ActivitySpectate(Box<ActivitySpectateData>),
+    ActivityJoinRequest(Box<ActivityJoinRequestData>),
+    CaptureShortcutChange(Box<CaptureShortcutChangeData>),
+    RelationshipUpdate(Box<RelationshipUpdateData>),
+    Overlay(Box<OverlayData>),
+    OverlayUpdate(Box<OverlayUpdateData>),
+    EntitlementCreate(Box<EntitlementCreateData>),
+    EntitlementDelete(Box<EntitlementDeleteData>),
+    LobbyUpdate(Box<LobbyUpdateData>),
+    LobbyDelete(Box<LobbyDeleteData>),
+    LobbyMemberConnect(Box<LobbyMemberConnectData>),
+    LobbyMemberUpdate(Box<LobbyMemberUpdateData>),
+    LobbyMemberDisconnect(Box<LobbyMemberDisconnectData>),
+    LobbyMessage(Box<LobbyMessageData>),
+    #[cfg(feature = "embedded-activity")]
+    ActivityInstanceParticipantsUpdate(Box<ActivityInstanceParticipantsUpdateData>),
+    #[cfg(feature = "embedded-activity")]
+    CurrentUserUpdate(Box<CurrentUserUpdateData>),
+    #[cfg(feature = "embedded-activity")]
+    OrientationUpdate(Box<OrientationUpdateData>),
+    /// Data of a response that could not be deserialized into the type expected for its command;
+    /// returned in a [SdkClientError::UnexpectedResponse]
+    Raw(Box<serde_json::Value>),
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
@@ -100,51 +154,81 @@ pub enum EventData {
     VoiceStateCreate(Box<VoiceStateCreateData>),
     VoiceStateUpdate(Box<VoiceStateUpdateData>),
     VoiceStateDelete(Box<VoiceStateDeleteData>),
-    // #[cfg(feature = "untested")]
-    // VoiceSettingsUpdate(VoiceSettingsUpdateArgs),
+    VoiceSettingsUpdate(Box<VoiceSettingsUpdateData>),
     VoiceConnectionStatus(Box<VoiceConnectionStatusData>),
     SpeakingStart(Box<SpeakingStartData>),
     SpeakingStop(Box<SpeakingStopData>),
     MessageCreate(Box<MessageCreateData>),
     MessageUpdate(Box<MessageUpdateData>),
     MessageDelete(Box<MessageDeleteData>),
-    // NotificationCreate(NotificationCreateArgs),
-    // #[cfg(feature = "untested")]
-    // ActivityJoin(ActivityJoinArgs),
-    // #[cfg(feature = "untested")]
-    // ActivitySpectate(ActivitySpectateArgs),
-    // #[cfg(feature = "untested")]
-    // ActivityJoinRequest(ActivityJoinRequestArgs),
+    NotificationCreate(Box<NotificationCreateData>),
+    ActivityJoin(Box<ActivityJoinData>),
+    ActivitySpectate(Box<ActivitySpectateData>),
+    ActivityJoinRequest(Box<ActivityJoinRequestData>),
+    CaptureShortcutChange(Box<CaptureShortcutChangeData>),
+    RelationshipUpdate(Box<RelationshipUpdateData>),
+    Overlay(Box<OverlayData>),
+    OverlayUpdate(Box<OverlayUpdateData>),
+    EntitlementCreate(Box<EntitlementCreateData>),
+    EntitlementDelete(Box<EntitlementDeleteData>),
+    LobbyUpdate(Box<LobbyUpdateData>),
+    LobbyDelete(Box<LobbyDeleteData>),
+    LobbyMemberConnect(Box<LobbyMemberConnectData>),
+    LobbyMemberUpdate(Box<LobbyMemberUpdateData>),
+    LobbyMemberDisconnect(Box<LobbyMemberDisconnectData>),
+    LobbyMessage(Box<LobbyMessageData>),
+    #[cfg(feature = "embedded-activity")]
+    ActivityInstanceParticipantsUpdate(Box<ActivityInstanceParticipantsUpdateData>),
+    #[cfg(feature = "embedded-activity")]
+    CurrentUserUpdate(Box<CurrentUserUpdateData>),
+    #[cfg(feature = "embedded-activity")]
+    OrientationUpdate(Box<OrientationUpdateData>),
 }
 
-impl From<Data> for EventData {
-    fn from(data: Data) -> Self {
-        match data {
-            Data::GuildStatus(data) => EventData::GuildStatus(data),
-            Data::GuildCreate(data) => EventData::GuildCreate(data),
-            Data::ChannelCreate(data) => EventData::ChannelCreate(data),
-            Data::VoiceChannelSelect(data) => EventData::VoiceChannelSelect(data),
-            Data::VoiceStateCreate(data) => EventData::VoiceStateCreate(data),
-            Data::VoiceStateUpdate(data) => EventData::VoiceStateUpdate(data),
-            Data::VoiceStateDelete(data) => EventData::VoiceStateDelete(data),
-            // #[cfg(feature = "untested")]
-            // Data::VoiceSettingsUpdate(args) => EventData::VoiceSettingsUpdate(args),
-            Data::VoiceConnectionStatus(data) => EventData::VoiceConnectionStatus(data),
-            Data::SpeakingStart(data) => EventData::SpeakingStart(data),
-            Data::SpeakingStop(data) => EventData::SpeakingStop(data),
-            Data::MessageCreate(data) => EventData::MessageCreate(data),
-            Data::MessageUpdate(data) => EventData::MessageUpdate(data),
-            Data::MessageDelete(data) => EventData::MessageDelete(data),
-            // Data::NotificationCreate(args) => EventData::NotificationCreate(args),
-            // #[cfg(feature = "untested")]
-            // Data::ActivityJoin(args) => EventData::ActivityJoin(args),
-            // #[cfg(feature = "untested")]
-            // Data::ActivitySpectate(args) => EventData::ActivitySpectate(args),
-            // #[cfg(feature = "untested")]
-            // Data::ActivityJoinRequest(args) => EventData::ActivityJoinRequest(args),
-            _ => {
-                panic!("Data variant {:?} cannot be converted to EventData", data)
+impl TryFrom<PayloadResponse> for EventData {
+    type Error = SdkClientError;
+
+    fn try_from(response: PayloadResponse) -> Result<Self, Self::Error> {
+        match response.0.data {
+            Some(Data::GuildStatus(data)) => Ok(EventData::GuildStatus(data)),
+            Some(Data::GuildCreate(data)) => Ok(EventData::GuildCreate(data)),
+            Some(Data::ChannelCreate(data)) => Ok(EventData::ChannelCreate(data)),
+            Some(Data::VoiceChannelSelect(data)) => Ok(EventData::VoiceChannelSelect(data)),
+            Some(Data::VoiceStateCreate(data)) => Ok(EventData::VoiceStateCreate(data)),
+            Some(Data::VoiceStateUpdate(data)) => Ok(EventData::VoiceStateUpdate(data)),
+            Some(Data::VoiceStateDelete(data)) => Ok(EventData::VoiceStateDelete(data)),
+            Some(Data::VoiceSettingsUpdate(data)) => Ok(EventData::VoiceSettingsUpdate(data)),
+            Some(Data::VoiceConnectionStatus(data)) => Ok(EventData::VoiceConnectionStatus(data)),
+            Some(Data::SpeakingStart(data)) => Ok(EventData::SpeakingStart(data)),
+            Some(Data::SpeakingStop(data)) => Ok(EventData::SpeakingStop(data)),
+            Some(Data::MessageCreate(data)) => Ok(EventData::MessageCreate(data)),
+            Some(Data::MessageUpdate(data)) => Ok(EventData::MessageUpdate(data)),
+            Some(Data::MessageDelete(data)) => Ok(EventData::MessageDelete(data)),
+            Some(Data::NotificationCreate(data)) => Ok(EventData::NotificationCreate(data)),
+            Some(Data::ActivityJoin(data)) => Ok(EventData::ActivityJoin(data)),
+            Some(Data::ActivitySpectate(data)) => Ok(EventData::ActivitySpectate(data)),
+            Some(Data::ActivityJoinRequest(data)) => Ok(EventData::ActivityJoinRequest(data)),
+            Some(Data::CaptureShortcutChange(data)) => Ok(EventData::CaptureShortcutChange(data)),
+            Some(Data::RelationshipUpdate(data)) => Ok(EventData::RelationshipUpdate(data)),
+            Some(Data::Overlay(data)) => Ok(EventData::Overlay(data)),
+            Some(Data::OverlayUpdate(data)) => Ok(EventData::OverlayUpdate(data)),
+            Some(Data::EntitlementCreate(data)) => Ok(EventData::EntitlementCreate(data)),
+            Some(Data::EntitlementDelete(data)) => Ok(EventData::EntitlementDelete(data)),
+            Some(Data::LobbyUpdate(data)) => Ok(EventData::LobbyUpdate(data)),
+            Some(Data::LobbyDelete(data)) => Ok(EventData::LobbyDelete(data)),
+            Some(Data::LobbyMemberConnect(data)) => Ok(EventData::LobbyMemberConnect(data)),
+            Some(Data::LobbyMemberUpdate(data)) => Ok(EventData::LobbyMemberUpdate(data)),
+            Some(Data::LobbyMemberDisconnect(data)) => Ok(EventData::LobbyMemberDisconnect(data)),
+            Some(Data::LobbyMessage(data)) => Ok(EventData::LobbyMessage(data)),
+            #[cfg(feature = "embedded-activity")]
+            Some(Data::ActivityInstanceParticipantsUpdate(data)) => {
+                Ok(EventData::ActivityInstanceParticipantsUpdate(data))
             }
+            #[cfg(feature = "embedded-activity")]
+            Some(Data::CurrentUserUpdate(data)) => Ok(EventData::CurrentUserUpdate(data)),
+            #[cfg(feature = "embedded-activity")]
+            Some(Data::OrientationUpdate(data)) => Ok(EventData::OrientationUpdate(data)),
+            _ => Err(SdkClientError::UnexpectedResponse(Box::new(response))),
         }
     }
 }
@@ -165,12 +249,47 @@ pub enum Args {
     SelectTextChannel(SelectTextChannelArgs),
     GetVoiceSettings(GetVoiceSettingsArgs),
     SetVoiceSettings(SetVoiceSettingsArgs),
-    #[cfg(feature = "untested")]
     SetCertifiedDevices(SetCertifiedDevicesArgs),
     SetActivity(SetActivityArgs),
-    #[cfg(feature = "untested")]
+    CaptureShortcut(CaptureShortcutArgs),
+    GetRelationships(GetRelationshipsArgs),
+    ToggleVideo(ToggleVideoArgs),
+    ToggleScreenshare(ToggleScreenshareArgs),
+    SetConfig(SetConfigArgs),
+    GetSoundboardSounds(GetSoundboardSoundsArgs),
+    PlaySoundboardSound(PlaySoundboardSoundArgs),
+    SetOverlayLocked(SetOverlayLockedArgs),
+    OpenOverlayActivityInvite(OpenOverlayActivityInviteArgs),
+    OpenOverlayGuildInvite(OpenOverlayGuildInviteArgs),
+    OpenOverlayVoiceSettings(OpenOverlayVoiceSettingsArgs),
+    InviteBrowser(InviteBrowserArgs),
+    GuildTemplateBrowser(GuildTemplateBrowserArgs),
+    DeepLink(DeepLinkArgs),
+    GetEntitlements(GetEntitlementsArgs),
+    GetSkus(GetSkusArgs),
+    StartPurchase(StartPurchaseArgs),
+    CreateLobby(CreateLobbyArgs),
+    UpdateLobby(UpdateLobbyArgs),
+    DeleteLobby(DeleteLobbyArgs),
+    ConnectToLobby(ConnectToLobbyArgs),
+    DisconnectFromLobby(DisconnectFromLobbyArgs),
+    SendToLobby(SendToLobbyArgs),
+    UpdateLobbyMember(UpdateLobbyMemberArgs),
+    GetUser(GetUserArgs),
+    GetChannelPermissions(GetChannelPermissionsArgs),
+    #[cfg(feature = "embedded-activity")]
+    GetActivityInstanceConnectedParticipants(GetActivityInstanceConnectedParticipantsArgs),
+    #[cfg(feature = "embedded-activity")]
+    SetOrientationLockState(SetOrientationLockStateArgs),
+    #[cfg(feature = "embedded-activity")]
+    GetPlatformBehaviors(GetPlatformBehaviorsArgs),
+    #[cfg(feature = "embedded-activity")]
+    UserSettingsGetLocale(UserSettingsGetLocaleArgs),
+    #[cfg(feature = "embedded-activity")]
+    OpenShareMomentDialog(OpenShareMomentDialogArgs),
+    #[cfg(feature = "embedded-activity")]
+    InitiateImageUpload(InitiateImageUploadArgs),
     SendActivityJoinInvite(SendActivityJoinInviteArgs),
-    #[cfg(feature = "untested")]
     CloseActivityRequest(CloseActivityRequestArgs),
     GuildStatus(GuildStatusArgs),
     GuildCreate(GuildCreateArgs),
@@ -179,7 +298,6 @@ pub enum Args {
     VoiceStateCreate(VoiceStateCreateArgs),
     VoiceStateUpdate(VoiceStateUpdateArgs),
     VoiceStateDelete(VoiceStateDeleteArgs),
-    #[cfg(feature = "untested")]
     VoiceSettingsUpdate(VoiceSettingsUpdateArgs),
     VoiceConnectionStatus(VoiceConnectionStatusArgs),
     SpeakingStart(SpeakingStartArgs),
@@ -188,12 +306,27 @@ pub enum Args {
     MessageUpdate(MessageUpdateArgs),
     MessageDelete(MessageDeleteArgs),
     NotificationCreate(NotificationCreateArgs),
-    #[cfg(feature = "untested")]
     ActivityJoin(ActivityJoinArgs),
-    #[cfg(feature = "untested")]
     ActivitySpectate(ActivitySpectateArgs),
-    #[cfg(feature = "untested")]
     ActivityJoinRequest(ActivityJoinRequestArgs),
+    CaptureShortcutChange(CaptureShortcutChangeArgs),
+    RelationshipUpdate(RelationshipUpdateArgs),
+    Overlay(OverlayArgs),
+    OverlayUpdate(OverlayUpdateArgs),
+    EntitlementCreate(EntitlementCreateArgs),
+    EntitlementDelete(EntitlementDeleteArgs),
+    LobbyUpdate(LobbyUpdateArgs),
+    LobbyDelete(LobbyDeleteArgs),
+    LobbyMemberConnect(LobbyMemberConnectArgs),
+    LobbyMemberUpdate(LobbyMemberUpdateArgs),
+    LobbyMemberDisconnect(LobbyMemberDisconnectArgs),
+    LobbyMessage(LobbyMessageArgs),
+    #[cfg(feature = "embedded-activity")]
+    ActivityInstanceParticipantsUpdate(ActivityInstanceParticipantsUpdateArgs),
+    #[cfg(feature = "embedded-activity")]
+    CurrentUserUpdate(CurrentUserUpdateArgs),
+    #[cfg(feature = "embedded-activity")]
+    OrientationUpdate(OrientationUpdateArgs),
 }
 
 // TODO: is there a better way to handle empty brackets?
@@ -238,6 +371,38 @@ pub enum Command {
     SetActivity,
     SendActivityJoinInvite,
     CloseActivityRequest,
+    CaptureShortcut,
+    GetRelationships,
+    ToggleVideo,
+    ToggleScreenshare,
+    SetConfig,
+    GetSoundboardSounds,
+    PlaySoundboardSound,
+    SetOverlayLocked,
+    OpenOverlayActivityInvite,
+    OpenOverlayGuildInvite,
+    OpenOverlayVoiceSettings,
+    InviteBrowser,
+    GuildTemplateBrowser,
+    DeepLink,
+    GetEntitlements,
+    GetSkus,
+    StartPurchase,
+    CreateLobby,
+    UpdateLobby,
+    DeleteLobby,
+    ConnectToLobby,
+    DisconnectFromLobby,
+    SendToLobby,
+    UpdateLobbyMember,
+    GetUser,
+    GetChannelPermissions,
+    GetActivityInstanceConnectedParticipants,
+    SetOrientationLockState,
+    GetPlatformBehaviors,
+    UserSettingsGetLocale,
+    OpenShareMomentDialog,
+    InitiateImageUpload,
 }
 
 #[derive(
@@ -275,6 +440,21 @@ pub enum Event {
     ActivityJoin,
     ActivitySpectate,
     ActivityJoinRequest,
+    CaptureShortcutChange,
+    RelationshipUpdate,
+    Overlay,
+    OverlayUpdate,
+    EntitlementCreate,
+    EntitlementDelete,
+    LobbyUpdate,
+    LobbyDelete,
+    LobbyMemberConnect,
+    LobbyMemberUpdate,
+    LobbyMemberDisconnect,
+    LobbyMessage,
+    ActivityInstanceParticipantsUpdate,
+    CurrentUserUpdate,
+    OrientationUpdate,
 }
 
 #[derive(Debug, Copy, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
@@ -307,38 +487,62 @@ mod sealed {
 pub use activity::*;
 pub use auth::*;
 pub use channel::*;
-#[cfg(feature = "untested")]
 pub use device::*;
+#[cfg(feature = "embedded-activity")]
+pub use embedded::*;
 pub use error::*;
 pub use guild::*;
+pub use invite::*;
+pub use lobby::*;
 pub use message::*;
 pub use notification::*;
+pub use overlay::*;
 pub use ready::*;
+pub use relationship::*;
 pub use request::*;
 pub use response::*;
+pub use shortcut::*;
+pub use soundboard::*;
 pub use speaking::*;
+pub use store::*;
+pub use user::*;
 pub use voice::*;
 
 mod activity;
 mod auth;
 mod channel;
 mod device;
+#[cfg(feature = "embedded-activity")]
+mod embedded;
 mod error;
 mod guild;
+mod invite;
+mod lobby;
 mod macros;
 mod message;
 mod notification;
+mod overlay;
 mod ready;
+mod relationship;
 mod request;
 mod response;
+mod shortcut;
+mod soundboard;
 mod speaking;
+mod store;
+mod user;
 mod voice;
 
 pub mod common;
 
 #[cfg(test)]
 mod tests {
-    use super::{GetVoiceSettingsArgs, request::PayloadRequest};
+    use crate::client::SdkClientError;
+
+    use super::{
+        AuthorizeData, Command, Data, Event, EventData, GetVoiceSettingsArgs, Payload,
+        PayloadResponse, request::PayloadRequest,
+    };
 
     #[test]
     fn construct_args() {
@@ -347,4 +551,19 @@ mod tests {
             .build();
         let _s = serde_json::to_string(&payload).unwrap();
     }
+
+    #[test]
+    fn convert_non_event_data_to_event_data() {
+        let response = PayloadResponse(Payload {
+            cmd: Command::Dispatch,
+            nonce: None,
+            evt: Some(Event::MessageCreate),
+            data: Some(Data::Authorize(Box::new(AuthorizeData { code: None }))),
+            args: None,
+        });
+        let err = EventData::try_from(response.clone()).unwrap_err();
+        assert!(
+            matches!(err, SdkClientError::UnexpectedResponse(unexpected) if *unexpected == response)
+        );
+    }
 }
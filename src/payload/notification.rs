@@ -1,5 +1,40 @@
-use super::macros::{impl_empty_args_type, impl_event_args_type};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use super::{
+    common::message::Message,
+    macros::{impl_empty_args_type, impl_event_args_type},
+};
 
 impl_empty_args_type!(NotificationCreate);
 
 impl_event_args_type!(NotificationCreate);
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct NotificationCreateData {
+    pub channel_id: Option<String>,
+    pub message: Option<Message>,
+    pub icon_url: Option<Url>,
+    pub title: Option<String>,
+    pub body: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::NotificationCreateData;
+
+    #[test]
+    fn deserialize_notification_create() {
+        let payload = r##"{"channel_id":"199737254929760256","message":{"id":"199743874640379904","blocked":false,"content":"test message","author_color":"#ffffff","edited_timestamp":null,"timestamp":"2016-07-05T04:30:50.776Z","tts":false,"mentions":[],"mention_roles":[],"mention_everyone":false,"embeds":[],"attachments":[],"type":0,"pinned":false,"author":{"id":"53908232506183680","username":"Mason","discriminator":"1337","avatar":"a_bab14f271d565501444b2ca3be944b25","bot":false}},"icon_url":"https://cdn.discordapp.com/avatars/155149108183695360/94b8b2c3e7ba8e7d4d6e0c2ffea8c6b4.jpg","title":"test_user (#general)","body":"test message"}"##;
+        let data = serde_json::from_str::<NotificationCreateData>(payload).unwrap();
+        assert_eq!(data.channel_id, Some("199737254929760256".to_string()));
+        assert_eq!(data.title, Some("test_user (#general)".to_string()));
+        assert_eq!(data.body, Some("test message".to_string()));
+        assert_eq!(
+            data.message.unwrap().content,
+            Some("test message".to_string())
+        );
+    }
+}
@@ -0,0 +1,109 @@
+use bon::Builder;
+use serde::{Deserialize, Serialize};
+use serde_repr::{Deserialize_repr, Serialize_repr};
+
+use super::{
+    EmptyBracket,
+    macros::{impl_empty_args_type, impl_event_args_type, impl_request_args_type},
+};
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash, Builder)]
+pub struct SetOverlayLockedArgs {
+    locked: bool,
+    pid: u32,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct SetOverlayLockedData(pub Option<EmptyBracket>);
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash, Builder)]
+pub struct OpenOverlayActivityInviteArgs {
+    #[serde(rename = "type")]
+    action_type: ActivityActionType,
+    pid: u32,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct OpenOverlayActivityInviteData(pub Option<EmptyBracket>);
+
+/// The kind of invite the overlay opens with [OpenOverlayActivityInviteArgs]
+#[derive(Debug, Copy, Clone, Deserialize_repr, Serialize_repr, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum ActivityActionType {
+    Join = 1,
+    Spectate = 2,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash, Builder)]
+pub struct OpenOverlayGuildInviteArgs {
+    /// Invite code of the guild, e.g. `discord-developers` for `discord.gg/discord-developers`
+    #[builder(into)]
+    code: String,
+    pid: u32,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct OpenOverlayGuildInviteData(pub Option<EmptyBracket>);
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash, Builder)]
+pub struct OpenOverlayVoiceSettingsArgs {
+    pid: u32,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct OpenOverlayVoiceSettingsData(pub Option<EmptyBracket>);
+
+/// Data sent with the current state of the overlay
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct OverlayData {
+    pub enabled: Option<bool>,
+    pub locked: Option<bool>,
+}
+
+/// Data sent when the overlay is enabled/disabled or locked/unlocked, which carries the same
+/// state as [OverlayData]
+pub type OverlayUpdateData = OverlayData;
+
+impl_request_args_type!(SetOverlayLocked);
+impl_request_args_type!(OpenOverlayActivityInvite);
+impl_request_args_type!(OpenOverlayGuildInvite);
+impl_request_args_type!(OpenOverlayVoiceSettings);
+
+impl_empty_args_type!(Overlay);
+impl_empty_args_type!(OverlayUpdate);
+
+impl_event_args_type!(Overlay);
+impl_event_args_type!(OverlayUpdate);
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::{
+        ActivityActionType, OpenOverlayActivityInviteArgs, OverlayUpdateData, SetOverlayLockedArgs,
+    };
+
+    #[test]
+    fn serialize_overlay_commands() {
+        let args = SetOverlayLockedArgs::builder()
+            .locked(true)
+            .pid(1234)
+            .build();
+        let args = serde_json::to_string(&args).unwrap();
+        assert_eq!(args, r##"{"locked":true,"pid":1234}"##);
+        let args = OpenOverlayActivityInviteArgs::builder()
+            .action_type(ActivityActionType::Join)
+            .pid(1234)
+            .build();
+        let args = serde_json::to_string(&args).unwrap();
+        assert_eq!(args, r##"{"type":1,"pid":1234}"##);
+    }
+
+    #[test]
+    fn deserialize_overlay_update() {
+        let payload = r##"{"enabled":true,"locked":false}"##;
+        let data = serde_json::from_str::<OverlayUpdateData>(payload).unwrap();
+        assert_eq!(data.enabled, Some(true));
+        assert_eq!(data.locked, Some(false));
+    }
+}
@@ -0,0 +1,61 @@
+use serde::{Deserialize, Serialize};
+
+use super::{
+    common::relationship::Relationship,
+    macros::{impl_empty_args_type, impl_event_args_type, impl_request_args_type},
+};
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct GetRelationshipsData {
+    pub relationships: Option<Vec<Relationship>>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct RelationshipUpdateData(pub Relationship);
+
+impl_empty_args_type!(GetRelationships);
+impl_empty_args_type!(RelationshipUpdate);
+
+impl_request_args_type!(GetRelationships);
+
+impl_event_args_type!(RelationshipUpdate);
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use crate::payload::common::relationship::{RelationshipType, Status};
+
+    use super::{GetRelationshipsData, RelationshipUpdateData};
+
+    #[test]
+    fn deserialize_get_relationships() {
+        let payload = r##"{"relationships":[{"type":1,"user":{"id":"53908232506183680","username":"Mason","discriminator":"1337","avatar":null},"presence":{"status":"online","activity":{"name":"Rocket League","type":0,"application_id":"379286085710381999","state":"In a Match","details":"Ranked Duos","timestamps":{"start":1507665886}}}},{"type":2,"user":{"id":"158284148040138752","username":"day2","discriminator":"0","avatar":null},"presence":{"status":"offline"}}]}"##;
+        let data = serde_json::from_str::<GetRelationshipsData>(payload).unwrap();
+        let relationships = data.relationships.unwrap();
+        assert_eq!(
+            relationships[0].relationship_type,
+            Some(RelationshipType::Friend)
+        );
+        assert_eq!(
+            relationships[0].friend_activity().unwrap().name,
+            Some("Rocket League".to_string())
+        );
+        assert_eq!(
+            relationships[1].presence.as_ref().unwrap().status,
+            Some(Status::Offline)
+        );
+        assert!(relationships[1].friend_activity().is_none());
+    }
+
+    #[test]
+    fn deserialize_relationship_update() {
+        let payload = r##"{"type":3,"user":{"id":"53908232506183680","username":"Mason","discriminator":"1337","avatar":null},"presence":{"status":"dnd","activity":null}}"##;
+        let data = serde_json::from_str::<RelationshipUpdateData>(payload).unwrap();
+        assert_eq!(
+            data.0.relationship_type,
+            Some(RelationshipType::PendingIncoming)
+        );
+        assert_eq!(data.0.presence.unwrap().status, Some(Status::Dnd));
+    }
+}
@@ -229,7 +229,8 @@ mod tests {
                             .activity_type(ActivityType::Competing)
                             .state("abcdef")
                             .party(Party::builder().size([1, 4]).build())
-                            .call(),
+                            .call()
+                            .unwrap(),
                     )
                     .build(),
             )
@@ -0,0 +1,116 @@
+use bon::Builder;
+use serde::{Deserialize, Serialize};
+use strum_macros::EnumString;
+
+use super::{
+    common::voice::{ShortcutKeyCombo, VoiceSettingsMode},
+    macros::{impl_empty_args_type, impl_event_args_type, impl_request_args_type},
+};
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash, Builder)]
+pub struct CaptureShortcutArgs {
+    action: CaptureShortcutAction,
+}
+
+impl CaptureShortcutArgs {
+    /// Starts capturing the keys pressed by the user
+    pub const fn start() -> Self {
+        Self {
+            action: CaptureShortcutAction::Start,
+        }
+    }
+
+    /// Stops capturing the keys pressed by the user
+    pub const fn stop() -> Self {
+        Self {
+            action: CaptureShortcutAction::Stop,
+        }
+    }
+}
+
+#[derive(
+    Debug,
+    Copy,
+    Clone,
+    Deserialize,
+    Serialize,
+    PartialEq,
+    Eq,
+    Hash,
+    EnumString,
+    strum_macros::Display,
+)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+pub enum CaptureShortcutAction {
+    Start,
+    Stop,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct CaptureShortcutData(pub Option<CaptureShortcutChangeData>);
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct CaptureShortcutChangeData {
+    pub shortcut: Option<Vec<ShortcutKeyCombo>>,
+}
+
+impl From<CaptureShortcutChangeData> for VoiceSettingsMode {
+    fn from(value: CaptureShortcutChangeData) -> Self {
+        VoiceSettingsMode {
+            mode_type: None,
+            auto_threshold: None,
+            threshold: None,
+            shortcut: value.shortcut,
+            delay: None,
+        }
+    }
+}
+
+impl_empty_args_type!(CaptureShortcutChange);
+
+impl_request_args_type!(CaptureShortcut);
+
+impl_event_args_type!(CaptureShortcutChange);
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use crate::payload::{
+        SetVoiceSettingsArgs,
+        common::voice::{KeyType, ModeType, VoiceSettings, VoiceSettingsMode},
+    };
+
+    use super::{CaptureShortcutArgs, CaptureShortcutChangeData};
+
+    #[test]
+    fn construct_capture_shortcut_args() {
+        let args = serde_json::to_string(&CaptureShortcutArgs::start()).unwrap();
+        assert_eq!(args, r#"{"action":"START"}"#);
+        let args = serde_json::to_string(&CaptureShortcutArgs::stop()).unwrap();
+        assert_eq!(args, r#"{"action":"STOP"}"#);
+    }
+
+    #[test]
+    fn deserialize_capture_shortcut_change() {
+        let payload = r##"{"shortcut":[{"type":2,"code":162,"name":"ctrl"},{"type":0,"code":12,"name":"i"}]}"##;
+        let data = serde_json::from_str::<CaptureShortcutChangeData>(payload).unwrap();
+        let shortcut = data.shortcut.as_ref().unwrap();
+        assert_eq!(shortcut.len(), 2);
+        assert_eq!(shortcut[0].key_type, KeyType::KeyboardModifierKey);
+        assert_eq!(shortcut[1].name.as_str(), "i");
+    }
+
+    #[test]
+    fn capture_shortcut_change_into_voice_settings() {
+        let payload = r##"{"shortcut":[{"type":1,"code":4,"name":"mouse4"}]}"##;
+        let data = serde_json::from_str::<CaptureShortcutChangeData>(payload).unwrap();
+        let mut mode = VoiceSettingsMode::from(data);
+        mode.mode_type = Some(ModeType::PushToTalk);
+        let args = SetVoiceSettingsArgs::from(VoiceSettings::builder().mode(mode).build());
+        let args = serde_json::to_string(&args).unwrap();
+        assert!(args.contains(r#""type":"PUSH_TO_TALK""#));
+        assert!(args.contains(r#""shortcut":[{"type":1,"code":4,"name":"mouse4"}]"#));
+    }
+}
@@ -0,0 +1,71 @@
+use bon::Builder;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    EmptyBracket,
+    common::soundboard::SoundboardSound,
+    macros::{impl_empty_args_type, impl_request_args_type},
+};
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct GetSoundboardSoundsData(pub Vec<SoundboardSound>);
+
+impl GetSoundboardSoundsData {
+    /// Returns the sounds that belong to the guild with the given id
+    pub fn for_guild<'a>(
+        &'a self,
+        guild_id: &'a str,
+    ) -> impl Iterator<Item = &'a SoundboardSound> + 'a {
+        self.0
+            .iter()
+            .filter(move |sound| sound.guild_id.as_deref() == Some(guild_id))
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash, Builder)]
+pub struct PlaySoundboardSoundArgs {
+    #[builder(into)]
+    pub guild_id: String,
+    #[builder(into)]
+    pub sound_id: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct PlaySoundboardSoundData(pub Option<EmptyBracket>);
+
+impl_empty_args_type!(GetSoundboardSounds);
+
+impl_request_args_type!(GetSoundboardSounds);
+impl_request_args_type!(PlaySoundboardSound);
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::{GetSoundboardSoundsData, PlaySoundboardSoundArgs};
+
+    #[test]
+    fn deserialize_get_soundboard_sounds() {
+        let payload = r##"[{"name":"quack","sound_id":"1","volume":1.0,"emoji_id":null,"emoji_name":"🦆","override_path":"default_quack.mp3","guild_id":"0","user_id":"0","available":true},{"name":"airhorn","sound_id":"1267538891183882373","volume":0.5,"emoji_id":"1146932146425864314","emoji_name":null,"guild_id":"199737254929760256","user_id":"53908232506183680","available":false,"user":{"id":"53908232506183680","username":"Mason","discriminator":"0","avatar":null}}]"##;
+        let data = serde_json::from_str::<GetSoundboardSoundsData>(payload).unwrap();
+        assert_eq!(data.0.len(), 2);
+        let guild_sounds = data.for_guild("199737254929760256").collect::<Vec<_>>();
+        assert_eq!(guild_sounds.len(), 1);
+        assert_eq!(guild_sounds[0].name, Some("airhorn".to_string()));
+        assert_eq!(guild_sounds[0].available, Some(false));
+        assert_eq!(data.for_guild("0").count(), 1);
+    }
+
+    #[test]
+    fn serialize_play_soundboard_sound() {
+        let args = PlaySoundboardSoundArgs::builder()
+            .guild_id("199737254929760256")
+            .sound_id("1267538891183882373")
+            .build();
+        let args = serde_json::to_string(&args).unwrap();
+        assert_eq!(
+            args,
+            r##"{"guild_id":"199737254929760256","sound_id":"1267538891183882373"}"##
+        );
+    }
+}
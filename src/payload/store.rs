@@ -0,0 +1,112 @@
+use bon::Builder;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    common::entitlement::{Entitlement, Sku},
+    macros::{impl_empty_args_type, impl_event_args_type, impl_request_args_type},
+};
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct GetEntitlementsData {
+    pub entitlements: Vec<Entitlement>,
+}
+
+impl GetEntitlementsData {
+    /// Whether the user currently owns the SKU with the given id through an active entitlement
+    ///
+    /// Entitlements of consumable SKUs that have already been consumed are not counted.
+    pub fn owns_sku(&self, sku_id: &str) -> bool {
+        let now = Utc::now();
+        self.entitlements.iter().any(|entitlement| {
+            entitlement.sku_id.as_deref() == Some(sku_id)
+                && entitlement.consumed != Some(true)
+                && entitlement.is_active_at(now)
+        })
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct GetSkusData {
+    pub skus: Vec<Sku>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash, Builder)]
+pub struct StartPurchaseArgs {
+    #[builder(into)]
+    sku_id: String,
+    pid: u32,
+}
+
+/// Entitlements granted by the purchase; [None] if the user closed the purchase flow
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct StartPurchaseData(pub Option<Vec<Entitlement>>);
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct EntitlementCreateData {
+    pub entitlement: Option<Entitlement>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct EntitlementDeleteData {
+    pub entitlement: Option<Entitlement>,
+}
+
+impl_empty_args_type!(GetEntitlements);
+impl_empty_args_type!(GetSkus);
+impl_empty_args_type!(EntitlementCreate);
+impl_empty_args_type!(EntitlementDelete);
+
+impl_request_args_type!(GetEntitlements);
+impl_request_args_type!(GetSkus);
+impl_request_args_type!(StartPurchase);
+
+impl_event_args_type!(EntitlementCreate);
+impl_event_args_type!(EntitlementDelete);
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use crate::payload::common::entitlement::{EntitlementType, SkuType};
+
+    use super::{EntitlementCreateData, GetEntitlementsData, GetSkusData};
+
+    #[test]
+    fn deserialize_get_entitlements() {
+        let payload = r##"{"entitlements":[{"id":"1019653849998299136","sku_id":"1019475255913222144","application_id":"1019370614521200640","user_id":"771129655544643584","gift_code_flags":0,"type":1,"consumed":false,"deleted":false},{"id":"1019653849998299137","sku_id":"1019475255913222145","application_id":"1019370614521200640","user_id":"771129655544643584","type":4,"ends_at":"2020-01-01T00:00:00.000000+00:00","deleted":false}]}"##;
+        let data = serde_json::from_str::<GetEntitlementsData>(payload).unwrap();
+        assert_eq!(
+            data.entitlements[0].entitlement_type,
+            Some(EntitlementType::Purchase)
+        );
+        assert!(data.owns_sku("1019475255913222144"));
+        assert!(!data.owns_sku("1019475255913222145"));
+        assert!(!data.owns_sku("0"));
+    }
+
+    #[test]
+    fn consumed_entitlement_does_not_own_sku() {
+        let payload = r##"{"entitlements":[{"id":"1019653849998299136","sku_id":"1019475255913222144","application_id":"1019370614521200640","user_id":"771129655544643584","type":1,"consumed":true,"deleted":false}]}"##;
+        let data = serde_json::from_str::<GetEntitlementsData>(payload).unwrap();
+        assert!(!data.owns_sku("1019475255913222144"));
+    }
+
+    #[test]
+    fn deserialize_get_skus() {
+        let payload = r##"{"skus":[{"id":"1019475255913222144","name":"Soundtrack","type":2,"price":{"amount":499,"currency":"usd"},"application_id":"1019370614521200640","flags":0,"release_date":null}]}"##;
+        let data = serde_json::from_str::<GetSkusData>(payload).unwrap();
+        assert_eq!(data.skus[0].sku_type, Some(SkuType::Dlc));
+        assert_eq!(data.skus[0].price.as_ref().unwrap().amount, Some(499));
+    }
+
+    #[test]
+    fn deserialize_entitlement_create() {
+        let payload = r##"{"entitlement":{"id":"1019653849998299136","sku_id":"1019475255913222144","application_id":"1019370614521200640","user_id":"771129655544643584","type":1}}"##;
+        let data = serde_json::from_str::<EntitlementCreateData>(payload).unwrap();
+        assert_eq!(
+            data.entitlement.unwrap().sku_id,
+            Some("1019475255913222144".to_string())
+        );
+    }
+}
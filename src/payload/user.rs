@@ -0,0 +1,32 @@
+use bon::Builder;
+use serde::{Deserialize, Serialize};
+
+use super::{common::user::User, macros::impl_request_args_type};
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash, Builder)]
+pub struct GetUserArgs {
+    #[builder(into)]
+    id: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct GetUserData(pub User);
+
+impl_request_args_type!(GetUser);
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::{GetUserArgs, GetUserData};
+
+    #[test]
+    fn serialize_get_user() {
+        let args = GetUserArgs::builder().id("53908232506183680").build();
+        let args = serde_json::to_string(&args).unwrap();
+        assert_eq!(args, r##"{"id":"53908232506183680"}"##);
+        let payload = r##"{"id":"53908232506183680","username":"Mason","discriminator":"0","global_name":"Mason","avatar":null,"bot":false,"flags":0,"premium_type":0}"##;
+        let data = serde_json::from_str::<GetUserData>(payload).unwrap();
+        assert_eq!(data.0.username, Some("Mason".to_string()));
+    }
+}
@@ -6,7 +6,7 @@ use serde_with::skip_serializing_none;
 use crate::payload::common::{channel::impl_channel_id_type, pan::Pan, voice::VoiceSettings};
 
 use super::{
-    GetChannelData,
+    EmptyBracket, GetChannelData,
     common::{
         user::User,
         voice::{Ping, State, VoiceConnectionState},
@@ -97,6 +97,26 @@ pub struct SetVoiceSettingsData(pub VoiceSettings);
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
 pub struct GetVoiceSettingsData(pub VoiceSettings);
 
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct VoiceSettingsUpdateData(pub VoiceSettings);
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct ToggleVideoData(pub Option<EmptyBracket>);
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct ToggleScreenshareData(pub Option<EmptyBracket>);
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash, Builder)]
+pub struct SetConfigArgs {
+    /// Whether the picture-in-picture window of an activity should be interactive
+    pub use_interactive_pip: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct SetConfigData {
+    pub use_interactive_pip: Option<bool>,
+}
+
 impl_channel_id_type!(VoiceStateCreateArgs);
 impl_channel_id_type!(VoiceStateUpdateArgs);
 impl_channel_id_type!(VoiceStateDeleteArgs);
@@ -106,18 +126,22 @@ impl_empty_args_type!(GetSelectedVoiceChannel);
 impl_empty_args_type!(VoiceChannelSelect);
 impl_empty_args_type!(VoiceSettingsUpdate);
 impl_empty_args_type!(VoiceConnectionStatus);
+impl_empty_args_type!(ToggleVideo);
+impl_empty_args_type!(ToggleScreenshare);
 
 impl_request_args_type!(SetUserVoiceSettings);
 impl_request_args_type!(GetVoiceSettings);
 impl_request_args_type!(SetVoiceSettings);
 impl_request_args_type!(SelectVoiceChannel);
 impl_request_args_type!(GetSelectedVoiceChannel);
+impl_request_args_type!(ToggleVideo);
+impl_request_args_type!(ToggleScreenshare);
+impl_request_args_type!(SetConfig);
 
 impl_event_args_type!(VoiceChannelSelect);
 impl_event_args_type!(VoiceStateCreate);
 impl_event_args_type!(VoiceStateUpdate);
 impl_event_args_type!(VoiceStateDelete);
-#[cfg(feature = "untested")]
 impl_event_args_type!(VoiceSettingsUpdate);
 impl_event_args_type!(VoiceConnectionStatus);
 
@@ -126,3 +150,43 @@ impl From<VoiceSettings> for SetVoiceSettingsArgs {
         Self(value)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use crate::payload::common::voice::{KeyType, ModeType};
+
+    use super::{SetConfigArgs, SetConfigData, ToggleVideoData, VoiceSettingsUpdateData};
+
+    #[test]
+    fn deserialize_voice_settings_update() {
+        let payload = r##"{"input":{"available_devices":[{"id":"default","name":"Default"},{"id":"Built-in Microphone","name":"Built-in Microphone"}],"device_id":"default","volume":49.803921580314636},"output":{"available_devices":[{"id":"default","name":"Default"},{"id":"Built-in Output","name":"Built-in Output"}],"device_id":"default","volume":93.00000071525574},"mode":{"type":"VOICE_ACTIVITY","auto_threshold":true,"threshold":-46.92622950819673,"shortcut":[{"type":0,"code":12,"name":"i"}],"delay":98.36065573770492},"automatic_gain_control":false,"echo_cancellation":false,"noise_suppression":false,"qos":false,"silence_warning":false,"deaf":false,"mute":true}"##;
+        let data = serde_json::from_str::<VoiceSettingsUpdateData>(payload).unwrap();
+        assert_eq!(data.0.mute, Some(true));
+        assert_eq!(data.0.deaf, Some(false));
+        assert_eq!(
+            data.0.input.unwrap().0.device_id,
+            Some("default".to_string())
+        );
+        let mode = data.0.mode.unwrap();
+        assert_eq!(mode.mode_type, Some(ModeType::VoiceActivity));
+        assert_eq!(mode.shortcut.unwrap()[0].key_type, KeyType::KeyboardKey);
+    }
+
+    #[test]
+    fn serialize_set_config() {
+        let args = SetConfigArgs::builder().use_interactive_pip(true).build();
+        let args = serde_json::to_string(&args).unwrap();
+        assert_eq!(args, r##"{"use_interactive_pip":true}"##);
+        let payload = r##"{"use_interactive_pip":true}"##;
+        let data = serde_json::from_str::<SetConfigData>(payload).unwrap();
+        assert_eq!(data.use_interactive_pip, Some(true));
+    }
+
+    #[test]
+    fn deserialize_toggle_video() {
+        let data = serde_json::from_str::<ToggleVideoData>("null").unwrap();
+        assert_eq!(data, ToggleVideoData(None));
+    }
+}
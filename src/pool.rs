@@ -19,17 +19,38 @@ use uuid::Uuid;
 use crate::{
     codec::Frame,
     payload::{
-        AuthenticateData, AuthorizeData, ChannelCreateData, Command, Data, ErrorData, Event,
-        GetChannelData, GetChannelsData, GetGuildData, GetGuildsData, GetSelectedVoiceChannelData,
-        GetVoiceSettingsData, GuildCreateData, GuildStatusData, MessageCreateData,
-        MessageDeleteData, MessageUpdateData, Payload, PayloadResponse, ReadyData, Request,
-        SelectTextChannelData, SelectVoiceChannelData, SetActivityData, SetUserVoiceSettingsData,
-        SetVoiceSettingsData, SpeakingStartData, SpeakingStopData, SubscribeData, UnsubscribeData,
-        VoiceChannelSelectData, VoiceConnectionStatusData, VoiceStateCreateData,
-        VoiceStateDeleteData, VoiceStateUpdateData, common::opcode::Opcode,
+        ActivityJoinData, ActivityJoinRequestData, ActivitySpectateData, AuthenticateData,
+        AuthorizeData, CaptureShortcutChangeData, CaptureShortcutData, ChannelCreateData,
+        CloseActivityRequestData, Command, ConnectToLobbyData, CreateLobbyData, Data, DeepLinkData,
+        DeleteLobbyData, DisconnectFromLobbyData, EntitlementCreateData, EntitlementDeleteData,
+        ErrorData, Event, GetChannelData, GetChannelPermissionsData, GetChannelsData,
+        GetEntitlementsData, GetGuildData, GetGuildsData, GetRelationshipsData,
+        GetSelectedVoiceChannelData, GetSkusData, GetSoundboardSoundsData, GetUserData,
+        GetVoiceSettingsData, GuildCreateData, GuildStatusData, GuildTemplateBrowserData,
+        InviteBrowserData, LobbyDeleteData, LobbyMemberConnectData, LobbyMemberDisconnectData,
+        LobbyMemberUpdateData, LobbyMessageData, LobbyUpdateData, MessageCreateData,
+        MessageDeleteData, MessageUpdateData, NotificationCreateData,
+        OpenOverlayActivityInviteData, OpenOverlayGuildInviteData, OpenOverlayVoiceSettingsData,
+        OverlayData, OverlayUpdateData, Payload, PayloadResponse, PlaySoundboardSoundData,
+        ReadyData, RelationshipUpdateData, Request, SelectTextChannelData, SelectVoiceChannelData,
+        SendActivityJoinInviteData, SendToLobbyData, SetActivityData, SetCertifiedDevicesData,
+        SetConfigData, SetOverlayLockedData, SetUserVoiceSettingsData, SetVoiceSettingsData,
+        SpeakingStartData, SpeakingStopData, StartPurchaseData, SubscribeData,
+        ToggleScreenshareData, ToggleVideoData, UnsubscribeData, UpdateLobbyData,
+        UpdateLobbyMemberData, VoiceChannelSelectData, VoiceConnectionStatusData,
+        VoiceSettingsUpdateData, VoiceStateCreateData, VoiceStateDeleteData, VoiceStateUpdateData,
+        common::opcode::Opcode,
     },
 };
 
+#[cfg(feature = "embedded-activity")]
+use crate::payload::{
+    ActivityInstanceParticipantsUpdateData, CurrentUserUpdateData,
+    GetActivityInstanceConnectedParticipantsData, GetPlatformBehaviorsData,
+    InitiateImageUploadData, OpenShareMomentDialogData, OrientationUpdateData,
+    SetOrientationLockStateData, UserSettingsGetLocaleData,
+};
+
 /// Generic Serde Client
 ///
 /// Used to send and receive either a serialization response or a deserialization response on a
@@ -192,6 +213,40 @@ pub(crate) fn deserialize(frame: &Frame) -> Result<PayloadResponse, SerdeProcess
                 .map_err(|err| SerdeProcessingError::Deserialization(err.to_string()))
         })
         .transpose()?;
+    let data = match deserialize_payload_data(&payload, cmd, evt) {
+        Ok(data) => data,
+        // a request is waiting on the nonce, so it gets the raw data instead of timing out
+        Err(err) if nonce.is_some() => {
+            error!(
+                "failed to deserialize data of response {:?}: {}",
+                nonce, err
+            );
+            payload
+                .get_mut("data")
+                .map(|data| Data::Raw(Box::new(data.take())))
+        }
+        Err(err) => return Err(err),
+    };
+
+    Ok(PayloadResponse(Payload {
+        cmd,
+        nonce,
+        evt,
+        data,
+        args: None,
+    }))
+}
+
+/// Deserialize the `data` of a payload into the type of its command or event
+///
+/// # Errors
+/// [SerdeProcessingError] is returned if the command or event is not supported or the data does
+/// not match its type
+fn deserialize_payload_data(
+    payload: &Value,
+    cmd: Command,
+    evt: Option<Event>,
+) -> Result<Option<Data>, SerdeProcessingError> {
     let data = {
         match (evt, cmd) {
             (Some(Event::Error), _) => {
@@ -222,6 +277,9 @@ pub(crate) fn deserialize(frame: &Frame) -> Result<PayloadResponse, SerdeProcess
                 Event::VoiceStateDelete => {
                     deserialize_data!(payload, VoiceStateDelete)
                 }
+                Event::VoiceSettingsUpdate => {
+                    deserialize_data!(payload, VoiceSettingsUpdate)
+                }
                 Event::VoiceConnectionStatus => {
                     deserialize_data!(payload, VoiceConnectionStatus)
                 }
@@ -240,14 +298,71 @@ pub(crate) fn deserialize(frame: &Frame) -> Result<PayloadResponse, SerdeProcess
                 Event::MessageDelete => {
                     deserialize_data!(payload, MessageDelete)
                 }
-                Event::NotificationCreate => todo!(),
-                Event::ActivityJoin => todo!(),
-                Event::ActivitySpectate => todo!(),
-                Event::ActivityJoinRequest => todo!(),
+                Event::NotificationCreate => {
+                    deserialize_data!(payload, NotificationCreate)
+                }
+                Event::ActivityJoin => {
+                    deserialize_data!(payload, ActivityJoin)
+                }
+                Event::ActivitySpectate => {
+                    deserialize_data!(payload, ActivitySpectate)
+                }
+                Event::ActivityJoinRequest => {
+                    deserialize_data!(payload, ActivityJoinRequest)
+                }
+                Event::CaptureShortcutChange => {
+                    deserialize_data!(payload, CaptureShortcutChange)
+                }
+                Event::RelationshipUpdate => {
+                    deserialize_data!(payload, RelationshipUpdate)
+                }
+                Event::Overlay => {
+                    deserialize_data!(payload, Overlay)
+                }
+                Event::OverlayUpdate => {
+                    deserialize_data!(payload, OverlayUpdate)
+                }
+                Event::EntitlementCreate => {
+                    deserialize_data!(payload, EntitlementCreate)
+                }
+                Event::EntitlementDelete => {
+                    deserialize_data!(payload, EntitlementDelete)
+                }
+                Event::LobbyUpdate => {
+                    deserialize_data!(payload, LobbyUpdate)
+                }
+                Event::LobbyDelete => {
+                    deserialize_data!(payload, LobbyDelete)
+                }
+                Event::LobbyMemberConnect => {
+                    deserialize_data!(payload, LobbyMemberConnect)
+                }
+                Event::LobbyMemberUpdate => {
+                    deserialize_data!(payload, LobbyMemberUpdate)
+                }
+                Event::LobbyMemberDisconnect => {
+                    deserialize_data!(payload, LobbyMemberDisconnect)
+                }
+                Event::LobbyMessage => {
+                    deserialize_data!(payload, LobbyMessage)
+                }
+                #[cfg(feature = "embedded-activity")]
+                Event::ActivityInstanceParticipantsUpdate => {
+                    deserialize_data!(payload, ActivityInstanceParticipantsUpdate)
+                }
+                #[cfg(feature = "embedded-activity")]
+                Event::CurrentUserUpdate => {
+                    deserialize_data!(payload, CurrentUserUpdate)
+                }
+                #[cfg(feature = "embedded-activity")]
+                Event::OrientationUpdate => {
+                    deserialize_data!(payload, OrientationUpdate)
+                }
                 _ => {
-                    panic!(
-                        "this should not happen since Event::Ready and Event::Error are covered previously; unless you have the untested feature on"
-                    )
+                    return Err(SerdeProcessingError::Unsupported {
+                        cmd,
+                        evt: Some(evt),
+                    });
                 }
             },
             (None, Command::Authorize) => {
@@ -295,19 +410,123 @@ pub(crate) fn deserialize(frame: &Frame) -> Result<PayloadResponse, SerdeProcess
             (None, Command::GetChannels) => {
                 deserialize_data!(payload, GetChannels)
             }
-            (_, _) => {
-                todo!()
+            (None, Command::SetCertifiedDevices) => {
+                deserialize_data!(payload, SetCertifiedDevices)
+            }
+            (None, Command::SendActivityJoinInvite) => {
+                deserialize_data!(payload, SendActivityJoinInvite)
+            }
+            (None, Command::CloseActivityRequest) => {
+                deserialize_data!(payload, CloseActivityRequest)
+            }
+            (None, Command::CaptureShortcut) => {
+                deserialize_data!(payload, CaptureShortcut)
+            }
+            (None, Command::GetRelationships) => {
+                deserialize_data!(payload, GetRelationships)
+            }
+            (None, Command::ToggleVideo) => {
+                deserialize_data!(payload, ToggleVideo)
+            }
+            (None, Command::ToggleScreenshare) => {
+                deserialize_data!(payload, ToggleScreenshare)
+            }
+            (None, Command::SetConfig) => {
+                deserialize_data!(payload, SetConfig)
+            }
+            (None, Command::GetSoundboardSounds) => {
+                deserialize_data!(payload, GetSoundboardSounds)
+            }
+            (None, Command::PlaySoundboardSound) => {
+                deserialize_data!(payload, PlaySoundboardSound)
+            }
+            (None, Command::SetOverlayLocked) => {
+                deserialize_data!(payload, SetOverlayLocked)
+            }
+            (None, Command::OpenOverlayActivityInvite) => {
+                deserialize_data!(payload, OpenOverlayActivityInvite)
+            }
+            (None, Command::OpenOverlayGuildInvite) => {
+                deserialize_data!(payload, OpenOverlayGuildInvite)
+            }
+            (None, Command::OpenOverlayVoiceSettings) => {
+                deserialize_data!(payload, OpenOverlayVoiceSettings)
+            }
+            (None, Command::InviteBrowser) => {
+                deserialize_data!(payload, InviteBrowser)
+            }
+            (None, Command::GuildTemplateBrowser) => {
+                deserialize_data!(payload, GuildTemplateBrowser)
+            }
+            (None, Command::DeepLink) => {
+                deserialize_data!(payload, DeepLink)
+            }
+            (None, Command::GetEntitlements) => {
+                deserialize_data!(payload, GetEntitlements)
+            }
+            (None, Command::GetSkus) => {
+                deserialize_data!(payload, GetSkus)
+            }
+            (None, Command::StartPurchase) => {
+                deserialize_data!(payload, StartPurchase)
+            }
+            (None, Command::CreateLobby) => {
+                deserialize_data!(payload, CreateLobby)
+            }
+            (None, Command::UpdateLobby) => {
+                deserialize_data!(payload, UpdateLobby)
+            }
+            (None, Command::DeleteLobby) => {
+                deserialize_data!(payload, DeleteLobby)
+            }
+            (None, Command::ConnectToLobby) => {
+                deserialize_data!(payload, ConnectToLobby)
+            }
+            (None, Command::DisconnectFromLobby) => {
+                deserialize_data!(payload, DisconnectFromLobby)
+            }
+            (None, Command::SendToLobby) => {
+                deserialize_data!(payload, SendToLobby)
+            }
+            (None, Command::UpdateLobbyMember) => {
+                deserialize_data!(payload, UpdateLobbyMember)
+            }
+            (None, Command::GetUser) => {
+                deserialize_data!(payload, GetUser)
+            }
+            (None, Command::GetChannelPermissions) => {
+                deserialize_data!(payload, GetChannelPermissions)
+            }
+            #[cfg(feature = "embedded-activity")]
+            (None, Command::GetActivityInstanceConnectedParticipants) => {
+                deserialize_data!(payload, GetActivityInstanceConnectedParticipants)
+            }
+            #[cfg(feature = "embedded-activity")]
+            (None, Command::SetOrientationLockState) => {
+                deserialize_data!(payload, SetOrientationLockState)
+            }
+            #[cfg(feature = "embedded-activity")]
+            (None, Command::GetPlatformBehaviors) => {
+                deserialize_data!(payload, GetPlatformBehaviors)
+            }
+            #[cfg(feature = "embedded-activity")]
+            (None, Command::UserSettingsGetLocale) => {
+                deserialize_data!(payload, UserSettingsGetLocale)
+            }
+            #[cfg(feature = "embedded-activity")]
+            (None, Command::OpenShareMomentDialog) => {
+                deserialize_data!(payload, OpenShareMomentDialog)
+            }
+            #[cfg(feature = "embedded-activity")]
+            (None, Command::InitiateImageUpload) => {
+                deserialize_data!(payload, InitiateImageUpload)
+            }
+            (evt, cmd) => {
+                return Err(SerdeProcessingError::Unsupported { cmd, evt });
             }
         }
     };
-
-    Ok(PayloadResponse(Payload {
-        cmd,
-        nonce,
-        evt,
-        data,
-        args: None,
-    }))
+    Ok(data)
 }
 
 /// Pool Error is returned when sending or receiving a message to or from the pool fails
@@ -330,6 +549,9 @@ pub enum SerdeProcessingError {
     /// Error that occurs when deserialization
     #[error("deserialization failed: {0}")]
     Deserialization(String),
+    /// Error that occurs when the payload is valid but its command or event is not supported
+    #[error("unsupported payload with command {cmd} and event {evt:?}")]
+    Unsupported { cmd: Command, evt: Option<Event> },
 }
 
 mod macros {
@@ -337,9 +559,9 @@ mod macros {
         ($payload: expr, $data_type: ident) => {
             paste::paste! {
                 $payload
-                    .get_mut("data")
+                    .get("data")
                     .map(|val| {
-                        serde_json::from_value::<[<$data_type Data>]>(val.take())
+                        <[<$data_type Data>] as serde::Deserialize>::deserialize(val)
                             .map_err(|err| SerdeProcessingError::Deserialization(err.to_string()))
                     })
                     .transpose()?
@@ -361,11 +583,11 @@ mod tests {
     use tokio::time::sleep;
 
     use crate::payload::{
-        ConnectRequest, GetChannelArgs, PayloadRequest,
+        ConnectRequest, Data, GetChannelArgs, PayloadRequest, SetCertifiedDevicesData,
         common::{channel::ChannelId, opcode::Opcode},
     };
 
-    use super::{Frame, Request, serialize, spawn_pool};
+    use super::{Frame, Request, SerdeProcessingError, deserialize, serialize, spawn_pool};
 
     #[inline(always)]
     const fn op(num: &u32) -> u32 {
@@ -441,4 +663,55 @@ mod tests {
         assert_eq!(expected_frame.opcode, actual_frame.opcode);
         assert_eq!(expected_frame.len, actual_frame.len);
     }
+
+    #[test]
+    fn test_deserialize_unsupported_payload() {
+        let payload = Bytes::from_static(b"{\"cmd\":\"DISPATCH\",\"data\":{},\"evt\":null}");
+        let frame = Frame {
+            opcode: Opcode::Frame,
+            len: payload.len() as u32,
+            payload,
+        };
+        let err = deserialize(&frame).unwrap_err();
+        assert!(matches!(
+            err,
+            SerdeProcessingError::Unsupported { evt: None, .. }
+        ));
+    }
+
+    #[test]
+    fn test_deserialize_malformed_response_keeps_raw_data() {
+        let payload = Bytes::from_static(
+            b"{\"cmd\":\"GET_GUILDS\",\"data\":{\"guilds\":\"none\"},\"evt\":null,\"nonce\":\"9524922c-3ba9-4be8-ae09-33a1a6e8d2b9\"}",
+        );
+        let frame = Frame {
+            opcode: Opcode::Frame,
+            len: payload.len() as u32,
+            payload,
+        };
+        let response = deserialize(&frame).unwrap();
+        assert_eq!(
+            response.0.data,
+            Some(Data::Raw(Box::new(serde_json::json!({"guilds": "none"}))))
+        );
+    }
+
+    #[test]
+    fn test_deserialize_set_certified_devices() {
+        let payload = Bytes::from_static(
+            b"{\"cmd\":\"SET_CERTIFIED_DEVICES\",\"data\":null,\"evt\":null,\"nonce\":\"9524922c-3ba9-4be8-ae09-33a1a6e8d2b9\"}",
+        );
+        let frame = Frame {
+            opcode: Opcode::Frame,
+            len: payload.len() as u32,
+            payload,
+        };
+        let response = deserialize(&frame).unwrap();
+        assert_eq!(
+            response.0.data,
+            Some(Data::SetCertifiedDevices(Box::new(
+                SetCertifiedDevicesData(None)
+            )))
+        );
+    }
 }
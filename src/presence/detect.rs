@@ -0,0 +1,268 @@
+//! Automatic game detection by scanning `/proc` for running processes.
+use std::{
+    fmt::{self, Debug},
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use tokio::time::sleep;
+use tracing::error;
+
+use super::{
+    ActivityTemplate,
+    rotation::{RATE_LIMIT_REQUESTS, RATE_LIMIT_WINDOW, RateLimiter},
+};
+use crate::{
+    client::SdkClient,
+    payload::{
+        SetActivityArgs,
+        common::activity::{ActivityRequest, ActivityValidationError},
+    },
+    task::AbortOnDrop,
+};
+
+/// How a [GameMatcher] recognizes a process
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum ProcessPattern {
+    /// Exact name of the process as found in `/proc/<pid>/comm`
+    Name(String),
+    /// Substring of the command line of the process as found in `/proc/<pid>/cmdline`
+    Cmdline(String),
+}
+
+/// Maps a running process to the activity shown while it is running
+#[derive(Clone)]
+pub struct GameMatcher {
+    pattern: ProcessPattern,
+    template: Arc<ActivityTemplate>,
+}
+
+impl GameMatcher {
+    /// Matches processes named exactly `name`, e.g. `hl2_linux`
+    ///
+    /// Linux truncates process names to 15 characters, so longer names are compared against
+    /// their first 15 characters.
+    ///
+    /// # Errors
+    /// The [ActivityValidationError] of `template` is returned if it does not build a valid
+    /// activity
+    pub fn process_name(
+        name: impl Into<String>,
+        template: impl Fn() -> Result<ActivityRequest, ActivityValidationError> + Send + Sync + 'static,
+    ) -> Result<Self, ActivityValidationError> {
+        let name = name.into().chars().take(15).collect();
+        Self::new(ProcessPattern::Name(name), template)
+    }
+
+    /// Matches processes whose command line contains `pattern`, e.g. `Celeste.exe` for a game
+    /// started through Wine
+    ///
+    /// # Errors
+    /// The [ActivityValidationError] of `template` is returned if it does not build a valid
+    /// activity
+    pub fn cmdline_contains(
+        pattern: impl Into<String>,
+        template: impl Fn() -> Result<ActivityRequest, ActivityValidationError> + Send + Sync + 'static,
+    ) -> Result<Self, ActivityValidationError> {
+        Self::new(ProcessPattern::Cmdline(pattern.into()), template)
+    }
+
+    fn new(
+        pattern: ProcessPattern,
+        template: impl Fn() -> Result<ActivityRequest, ActivityValidationError> + Send + Sync + 'static,
+    ) -> Result<Self, ActivityValidationError> {
+        template()?;
+        Ok(Self {
+            pattern,
+            template: Arc::new(template),
+        })
+    }
+
+    fn matches(&self, process: &Process) -> bool {
+        match &self.pattern {
+            ProcessPattern::Name(name) => process.name == *name,
+            ProcessPattern::Cmdline(pattern) => process.cmdline.contains(pattern.as_str()),
+        }
+    }
+}
+
+impl Debug for GameMatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GameMatcher")
+            .field("pattern", &self.pattern)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Sets the activity of the first running process matched by a [GameMatcher]
+///
+/// `/proc` is scanned on every interval. The activity is set with the pid of the detected
+/// process and cleared once the process exits. Matchers are checked in order, so earlier
+/// matchers take priority. A request that failed is retried on the next scan.
+#[derive(Debug)]
+pub struct GameDetector {
+    _task: AbortOnDrop,
+}
+
+impl GameDetector {
+    /// Starts scanning `/proc` every `interval`
+    pub fn start(client: SdkClient, matchers: Vec<GameMatcher>, interval: Duration) -> Self {
+        Self {
+            _task: AbortOnDrop::spawn(detect(client, matchers, interval)),
+        }
+    }
+}
+
+async fn detect(client: SdkClient, matchers: Vec<GameMatcher>, interval: Duration) {
+    let matchers = Arc::new(matchers);
+    let mut rate_limiter = RateLimiter::new(RATE_LIMIT_REQUESTS, RATE_LIMIT_WINDOW);
+    let mut detected: Option<u32> = None;
+    loop {
+        let scan_matchers = matchers.clone();
+        let found = tokio::task::spawn_blocking(move || scan(Path::new("/proc"), &scan_matchers))
+            .await
+            .unwrap_or_else(|err| {
+                error!("failed to scan /proc: {}", err);
+                None
+            });
+        if let Some(pid) = detected
+            && found.is_none_or(|(_, found_pid)| found_pid != pid)
+        {
+            rate_limiter.acquire().await;
+            clear_activity(&client, pid).await;
+            detected = None;
+        }
+        if let Some((index, pid)) = found
+            && detected.is_none()
+        {
+            match (matchers[index].template)() {
+                Ok(activity) => {
+                    let args = SetActivityArgs::builder()
+                        .pid(pid)
+                        .activity(Box::new(activity))
+                        .build();
+                    rate_limiter.acquire().await;
+                    match client.set_activity(args).await {
+                        Ok(_) => detected = Some(pid),
+                        Err(err) => error!("failed to set activity of detected game: {}", err),
+                    }
+                }
+                Err(err) => error!("invalid activity for detected game: {}", err),
+            }
+        }
+        sleep(interval).await;
+    }
+}
+
+async fn clear_activity(client: &SdkClient, pid: u32) {
+    let args = SetActivityArgs::builder().pid(pid).build();
+    if let Err(err) = client.set_activity(args).await {
+        error!("failed to clear activity of exited game: {}", err);
+    }
+}
+
+#[derive(Debug)]
+struct Process {
+    pid: u32,
+    name: String,
+    cmdline: String,
+}
+
+/// Returns the index of the first matcher with a running process and the pid of that process
+fn scan(proc_root: &Path, matchers: &[GameMatcher]) -> Option<(usize, u32)> {
+    let mut processes = fs::read_dir(proc_root)
+        .ok()?
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            let pid = entry.file_name().to_str()?.parse::<u32>().ok()?;
+            read_process(entry.path(), pid)
+        })
+        .collect::<Vec<_>>();
+    processes.sort_by_key(|process| process.pid);
+    matchers.iter().enumerate().find_map(|(index, matcher)| {
+        processes
+            .iter()
+            .find(|process| matcher.matches(process))
+            .map(|process| (index, process.pid))
+    })
+}
+
+fn read_process(path: PathBuf, pid: u32) -> Option<Process> {
+    let name = fs::read_to_string(path.join("comm")).ok()?;
+    let cmdline = fs::read(path.join("cmdline")).unwrap_or_default();
+    let cmdline = String::from_utf8_lossy(&cmdline)
+        .split('\0')
+        .filter(|arg| !arg.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    Some(Process {
+        pid,
+        name: name.trim_end().to_string(),
+        cmdline,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use pretty_assertions::assert_eq;
+
+    use crate::payload::common::activity::{Activity, ActivityType, ActivityValidationError};
+
+    use super::{GameMatcher, scan};
+
+    fn matcher(name: &str) -> GameMatcher {
+        GameMatcher::process_name(name, || {
+            Activity::request_builder()
+                .activity_type(ActivityType::Playing)
+                .call()
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn scan_proc_for_games() {
+        let root = std::env::temp_dir().join(format!("sdkcord-proc-{}", std::process::id()));
+        for (pid, comm, cmdline) in [
+            ("1", "systemd\n", "/sbin/init\0"),
+            ("42", "wine64-preload\n", "wine64\0C:\\Games\\Celeste.exe\0"),
+            ("100", "hl2_linux\n", "./hl2_linux\0-game\0tf\0"),
+            ("self", "sdkcord\n", ""),
+        ] {
+            let dir = root.join(pid);
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(dir.join("comm"), comm).unwrap();
+            fs::write(dir.join("cmdline"), cmdline).unwrap();
+        }
+        let celeste = GameMatcher::cmdline_contains("Celeste.exe", || {
+            Activity::request_builder()
+                .activity_type(ActivityType::Playing)
+                .call()
+        })
+        .unwrap();
+        assert_eq!(
+            scan(&root, &[matcher("hl2_linux"), celeste.clone()]),
+            Some((0, 100))
+        );
+        assert_eq!(scan(&root, &[matcher("dota2"), celeste]), Some((1, 42)));
+        assert_eq!(scan(&root, &[matcher("sdkcord-missing")]), None);
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn reject_invalid_template() {
+        let matcher = GameMatcher::process_name("hl2_linux", || {
+            Activity::request_builder()
+                .activity_type(ActivityType::Playing)
+                .state("x")
+                .call()
+        });
+        assert!(matches!(
+            matcher,
+            Err(ActivityValidationError::InvalidLength { field: "state", .. })
+        ));
+    }
+}
@@ -0,0 +1,345 @@
+//! High level rich presence management on top of the [SdkClient].
+//!
+//! The [PresenceManager] holds the desired activity of the application, fills in the process id
+//! and only sends a SET_ACTIVITY request when the activity actually changed. The
+//! [PresenceRotation] cycles the activity through several [RotationEntry] instead. On Linux, the
+//! `GameDetector` sets the activity while a configured game is running. With the `mpris` feature,
+//! the `MprisBridge` shows the track of the playing media player. The [PresenceFileWatcher] sets
+//! the activity from a [PresenceTemplate] file and reloads it when the file changes.
+//!
+//! The rotation, detector, bridge and file watcher update the activity from a background task.
+//! They delay their requests to stay within the SET_ACTIVITY rate limit of Discord, 5 requests
+//! per 20 seconds, and stop once they are dropped.
+#[cfg(target_os = "linux")]
+pub use detect::{GameDetector, GameMatcher};
+#[cfg(feature = "mpris")]
+pub use mpris::{MprisBridge, MprisError};
+pub use rotation::{MIN_ENTRY_DURATION, PresenceRotation, RotationEntry};
+pub use template::{PresenceFileWatcher, PresenceTemplate, TemplateContext, TemplateError};
+
+use crate::{
+    client::{SdkClient, SdkClientError},
+    payload::{
+        SetActivityArgs,
+        common::activity::{Activity, ActivityRequest, ActivityValidationError, Party, Timestamps},
+    },
+};
+use thiserror::Error;
+use tracing::error;
+
+/// Closure rebuilding an activity every time it is sent
+type ActivityTemplate = dyn Fn() -> Result<ActivityRequest, ActivityValidationError> + Send + Sync;
+
+/// Manages the rich presence of the current process
+///
+/// The activity is cleared when the manager is dropped inside of a tokio runtime.
+#[derive(Debug)]
+pub struct PresenceManager {
+    client: SdkClient,
+    pid: u32,
+    desired: Option<ActivityRequest>,
+    last_sent: Option<ActivityRequest>,
+}
+
+impl PresenceManager {
+    /// Creates a manager that sets the activity of the current process
+    pub fn new(client: SdkClient) -> Self {
+        Self::with_pid(client, std::process::id())
+    }
+
+    /// Creates a manager that sets the activity of the process with the given id
+    pub const fn with_pid(client: SdkClient, pid: u32) -> Self {
+        Self {
+            client,
+            pid,
+            desired: None,
+            last_sent: None,
+        }
+    }
+
+    /// Returns the desired activity
+    pub const fn activity(&self) -> Option<&ActivityRequest> {
+        self.desired.as_ref()
+    }
+
+    /// Replace the desired activity and send it if it changed
+    ///
+    /// Returns `true` if a request was sent.
+    ///
+    /// # Errors
+    /// A [PresenceError] is returned if the request fails
+    pub async fn set(&mut self, activity: ActivityRequest) -> Result<bool, PresenceError> {
+        self.desired = Some(activity);
+        self.flush().await
+    }
+
+    /// Apply a partial update to the desired activity and send it if it changed
+    ///
+    /// The updated activity is only validated if the desired activity was, so an activity built
+    /// with `skip_validation` stays unchecked.
+    ///
+    /// Returns `true` if a request was sent.
+    ///
+    /// # Errors
+    /// A [PresenceError] is returned if there is no desired activity, if the updated activity is
+    /// not valid or if the request fails
+    pub async fn update(&mut self, f: impl FnOnce(&mut Activity)) -> Result<bool, PresenceError> {
+        let desired = self.desired.clone().ok_or(PresenceError::NoActivity)?;
+        let skip_validation = desired.skips_validation();
+        let mut activity = desired.into_activity();
+        f(&mut activity);
+        let activity = if skip_validation {
+            ActivityRequest::unchecked(activity)
+        } else {
+            ActivityRequest::try_from(activity)?
+        };
+        self.set(activity).await
+    }
+
+    /// Update the state of the desired activity
+    ///
+    /// # Errors
+    /// See [PresenceManager::update]
+    pub async fn set_state(&mut self, state: impl Into<String>) -> Result<bool, PresenceError> {
+        let state = state.into();
+        self.update(|activity| activity.state = Some(state)).await
+    }
+
+    /// Update the details of the desired activity
+    ///
+    /// # Errors
+    /// See [PresenceManager::update]
+    pub async fn set_details(&mut self, details: impl Into<String>) -> Result<bool, PresenceError> {
+        let details = details.into();
+        self.update(|activity| activity.details = Some(details))
+            .await
+    }
+
+    /// Update the party of the desired activity
+    ///
+    /// # Errors
+    /// See [PresenceManager::update]
+    pub async fn set_party(&mut self, party: Option<Party>) -> Result<bool, PresenceError> {
+        self.update(|activity| activity.party = party).await
+    }
+
+    /// Update the timestamps of the desired activity, e.g. with [Timestamps::elapsed_since_now]
+    ///
+    /// # Errors
+    /// See [PresenceManager::update]
+    pub async fn set_timestamps(
+        &mut self,
+        timestamps: Option<Timestamps>,
+    ) -> Result<bool, PresenceError> {
+        self.update(|activity| activity.timestamps = timestamps)
+            .await
+    }
+
+    /// Clear the activity
+    ///
+    /// # Errors
+    /// A [PresenceError] is returned if the request fails
+    pub async fn clear(&mut self) -> Result<bool, PresenceError> {
+        self.desired = None;
+        self.flush().await
+    }
+
+    /// Replace the client after reconnecting to Discord and re-apply the desired activity
+    ///
+    /// # Errors
+    /// A [PresenceError] is returned if the request fails
+    pub async fn reconnect(&mut self, client: SdkClient) -> Result<bool, PresenceError> {
+        self.client = client;
+        self.last_sent = None;
+        if self.desired.is_none() {
+            return Ok(false);
+        }
+        self.flush().await
+    }
+
+    async fn flush(&mut self) -> Result<bool, PresenceError> {
+        if self.desired == self.last_sent {
+            return Ok(false);
+        }
+        let args = SetActivityArgs::builder()
+            .pid(self.pid)
+            .maybe_activity(self.desired.clone().map(Box::new))
+            .build();
+        self.client.set_activity(args).await?;
+        self.last_sent = self.desired.clone();
+        Ok(true)
+    }
+}
+
+impl Drop for PresenceManager {
+    fn drop(&mut self) {
+        if self.last_sent.is_none() {
+            return;
+        }
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        let client = self.client.clone();
+        let args = SetActivityArgs::builder().pid(self.pid).build();
+        handle.spawn(async move {
+            if let Err(err) = client.set_activity(args).await {
+                error!("failed to clear activity on drop: {}", err);
+            }
+        });
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum PresenceError {
+    #[error("no activity has been set")]
+    NoActivity,
+    #[error("invalid activity")]
+    Validation(#[from] ActivityValidationError),
+    #[error("failed to set the activity")]
+    Client(#[from] SdkClientError),
+}
+
+#[cfg(target_os = "linux")]
+mod detect;
+#[cfg(feature = "mpris")]
+mod mpris;
+mod rotation;
+mod template;
+
+#[cfg(all(test, unix))]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use serde_json::{Value, json};
+
+    use crate::{
+        payload::common::activity::{Activity, ActivityRequest, ActivityType, Party},
+        test_util::{MockDiscord, mock_client},
+    };
+
+    use super::{PresenceError, PresenceManager};
+
+    fn activity(details: &str) -> ActivityRequest {
+        Activity::request_builder()
+            .activity_type(ActivityType::Playing)
+            .details(details)
+            .state("In a match")
+            .call()
+            .unwrap()
+    }
+
+    /// Answers the next SET_ACTIVITY request and returns its arguments
+    async fn set_activity(discord: &mut MockDiscord) -> Value {
+        let request = discord.recv().await;
+        assert_eq!(request["cmd"], "SET_ACTIVITY");
+        let activity = request["args"].get("activity").cloned();
+        discord
+            .respond(&request, activity.unwrap_or(Value::Null))
+            .await;
+        request["args"].clone()
+    }
+
+    /// Runs a request of the manager while Discord answers the SET_ACTIVITY request it sends
+    macro_rules! with_response {
+        ($discord: ident, $request: expr) => {{
+            let (result, args) = tokio::join!($request, set_activity(&mut $discord));
+            (result.unwrap(), args)
+        }};
+    }
+
+    #[tokio::test]
+    async fn send_only_changed_activity() {
+        let (client, mut discord) = mock_client().await;
+        let mut manager = PresenceManager::with_pid(client, 1234);
+        let (sent, args) = with_response!(discord, manager.set(activity("Ranked")));
+        assert!(sent);
+        assert_eq!(args["pid"], 1234);
+        assert_eq!(args["activity"]["details"], "Ranked");
+        // the same activity is not sent again, so the next request is the changed one
+        assert!(!manager.set(activity("Ranked")).await.unwrap());
+        let (sent, args) = with_response!(discord, manager.set(activity("Casual")));
+        assert!(sent);
+        assert_eq!(args["activity"]["details"], "Casual");
+    }
+
+    #[tokio::test]
+    async fn apply_partial_updates() {
+        let (client, mut discord) = mock_client().await;
+        let mut manager = PresenceManager::with_pid(client, 1234);
+        assert!(matches!(
+            manager.set_state("Lobby").await,
+            Err(PresenceError::NoActivity)
+        ));
+        with_response!(discord, manager.set(activity("Ranked")));
+        let (_, args) = with_response!(discord, manager.set_state("Lobby"));
+        assert_eq!(args["activity"]["details"], "Ranked");
+        assert_eq!(args["activity"]["state"], "Lobby");
+        let party = Party::builder().id("party").size([1, 4]).build();
+        let (_, args) = with_response!(discord, manager.set_party(Some(party)));
+        assert_eq!(
+            args["activity"]["party"],
+            json!({"id": "party", "size": [1, 4]})
+        );
+        assert_eq!(args["activity"]["state"], "Lobby");
+        assert!(!manager.set_state("Lobby").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn keep_skipped_validation_on_update() {
+        let (client, mut discord) = mock_client().await;
+        let mut manager = PresenceManager::with_pid(client, 1234);
+        let unchecked = Activity::request_builder()
+            .activity_type(ActivityType::Playing)
+            .details("x")
+            .skip_validation(true)
+            .call()
+            .unwrap();
+        with_response!(discord, manager.set(unchecked));
+        let (sent, args) = with_response!(discord, manager.set_state("y"));
+        assert!(sent);
+        assert_eq!(args["activity"]["details"], "x");
+        assert_eq!(args["activity"]["state"], "y");
+
+        with_response!(discord, manager.set(activity("Ranked")));
+        assert!(matches!(
+            manager.set_state("y").await,
+            Err(PresenceError::Validation(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn clear_activity() {
+        let (client, mut discord) = mock_client().await;
+        let mut manager = PresenceManager::with_pid(client, 1234);
+        with_response!(discord, manager.set(activity("Ranked")));
+        let (sent, args) = with_response!(discord, manager.clear());
+        assert!(sent);
+        assert_eq!(args, json!({"pid": 1234}));
+        assert!(manager.activity().is_none());
+        assert!(!manager.clear().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn reapply_activity_on_reconnect() {
+        let (client, mut discord) = mock_client().await;
+        let mut manager = PresenceManager::with_pid(client, 1234);
+        with_response!(discord, manager.set(activity("Ranked")));
+        let (client, mut discord) = mock_client().await;
+        let (sent, args) = with_response!(discord, manager.reconnect(client));
+        assert!(sent);
+        assert_eq!(args["activity"]["details"], "Ranked");
+
+        let (client, _discord) = mock_client().await;
+        let mut manager = PresenceManager::with_pid(client, 1234);
+        let (client, _discord) = mock_client().await;
+        assert!(!manager.reconnect(client).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn clear_activity_on_drop() {
+        let (client, mut discord) = mock_client().await;
+        let mut manager = PresenceManager::with_pid(client, 1234);
+        with_response!(discord, manager.set(activity("Ranked")));
+        drop(manager);
+        assert_eq!(set_activity(&mut discord).await, json!({"pid": 1234}));
+    }
+}
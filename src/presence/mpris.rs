@@ -0,0 +1,556 @@
+//! Listening activity for the media players found on the D-Bus session bus through
+//! [MPRIS](https://specifications.freedesktop.org/mpris-spec/latest/).
+use std::{
+    collections::HashMap,
+    time::{Duration, SystemTime},
+};
+
+use thiserror::Error;
+use tokio::time::sleep;
+use tracing::error;
+use zbus::{
+    Connection, fdo::DBusProxy, proxy::CacheProperties, zvariant::OwnedValue, zvariant::Value,
+};
+
+use super::rotation::{RATE_LIMIT_REQUESTS, RATE_LIMIT_WINDOW, RateLimiter};
+use crate::{
+    client::SdkClient,
+    payload::{
+        SetActivityArgs,
+        common::activity::{
+            Activity, ActivityRequest, ActivityType, ActivityValidationError, Assets, Timestamps,
+            unix_millis,
+        },
+    },
+    task::AbortOnDrop,
+};
+
+/// Prefix of the bus names owned by MPRIS media players
+const MPRIS_PREFIX: &str = "org.mpris.MediaPlayer2.";
+/// Shortest string Discord accepts in an activity field
+const MIN_FIELD_LENGTH: usize = 2;
+/// Longest string Discord accepts in an activity field
+const MAX_FIELD_LENGTH: usize = 128;
+/// How far the start of the track may drift, e.g. because of a seek, before the activity is sent
+/// again
+const SEEK_TOLERANCE_MILLIS: u64 = 2_000;
+
+#[zbus::proxy(
+    interface = "org.mpris.MediaPlayer2.Player",
+    default_path = "/org/mpris/MediaPlayer2"
+)]
+trait Player {
+    #[zbus(property)]
+    fn playback_status(&self) -> zbus::Result<String>;
+
+    #[zbus(property)]
+    fn metadata(&self) -> zbus::Result<HashMap<String, OwnedValue>>;
+
+    #[zbus(property)]
+    fn position(&self) -> zbus::Result<i64>;
+}
+
+#[derive(Debug, Error)]
+pub enum MprisError {
+    #[error("failed to connect to the D-Bus session bus: {0}")]
+    Connection(#[from] zbus::Error),
+}
+
+/// Shows the track of the first playing MPRIS media player as a [ActivityType::Listening]
+/// activity
+///
+/// The players are polled on every interval. The activity is sent again when the track changes
+/// or the position jumps, and cleared once no player is playing anymore, e.g. when the track is
+/// paused.
+#[derive(Debug)]
+pub struct MprisBridge {
+    _task: AbortOnDrop,
+}
+
+impl MprisBridge {
+    /// Connects to the D-Bus session bus and starts polling the players every `interval`
+    ///
+    /// # Errors
+    /// A [MprisError] is returned if the session bus can't be reached
+    pub async fn start(client: SdkClient, interval: Duration) -> Result<Self, MprisError> {
+        let connection = Connection::session().await?;
+        Ok(Self::start_with_connection(client, connection, interval))
+    }
+
+    /// Starts polling the players found on `connection` every `interval`
+    pub fn start_with_connection(
+        client: SdkClient,
+        connection: Connection,
+        interval: Duration,
+    ) -> Self {
+        Self {
+            _task: AbortOnDrop::spawn(bridge(client, connection, std::process::id(), interval)),
+        }
+    }
+}
+
+/// Track that was last sent along with the time it started at, if the player reports its position
+#[derive(Debug)]
+struct Shown {
+    track: Track,
+    start: Option<u64>,
+}
+
+impl Shown {
+    /// Whether `track` starting at `start` is still shown, allowing for a small drift of the start
+    fn shows(&self, track: &Track, start: Option<u64>) -> bool {
+        self.track.same_track(track)
+            && match (self.start, start) {
+                (Some(shown), Some(start)) => shown.abs_diff(start) <= SEEK_TOLERANCE_MILLIS,
+                (shown, start) => shown.is_none() && start.is_none(),
+            }
+    }
+}
+
+async fn bridge(client: SdkClient, connection: Connection, pid: u32, interval: Duration) {
+    let mut rate_limiter = RateLimiter::new(RATE_LIMIT_REQUESTS, RATE_LIMIT_WINDOW);
+    let mut shown: Option<Shown> = None;
+    loop {
+        let track = playing_track(&connection).await.unwrap_or_else(|err| {
+            error!("failed to read MPRIS players: {}", err);
+            None
+        });
+        let now = unix_millis(SystemTime::now());
+        match track {
+            Some(track) => {
+                let start = track.start_millis(now);
+                let changed = shown
+                    .as_ref()
+                    .is_none_or(|shown| !shown.shows(&track, start));
+                if changed {
+                    match track.activity(now) {
+                        Ok(activity) => {
+                            let args = SetActivityArgs::builder()
+                                .pid(pid)
+                                .activity(Box::new(activity))
+                                .build();
+                            rate_limiter.acquire().await;
+                            match client.set_activity(args).await {
+                                Ok(_) => shown = Some(Shown { track, start }),
+                                Err(err) => {
+                                    error!("failed to set activity of MPRIS track: {}", err)
+                                }
+                            }
+                        }
+                        Err(err) => error!("invalid activity for MPRIS track: {}", err),
+                    }
+                }
+            }
+            None => {
+                if shown.take().is_some() {
+                    let args = SetActivityArgs::builder().pid(pid).build();
+                    rate_limiter.acquire().await;
+                    if let Err(err) = client.set_activity(args).await {
+                        error!("failed to clear activity of MPRIS track: {}", err);
+                    }
+                }
+            }
+        }
+        sleep(interval).await;
+    }
+}
+
+/// Returns the track of the first player, in bus name order, that is playing
+async fn playing_track(connection: &Connection) -> zbus::Result<Option<Track>> {
+    let mut players = DBusProxy::new(connection)
+        .await?
+        .list_names()
+        .await?
+        .into_iter()
+        .map(|name| name.to_string())
+        .filter(|name| name.starts_with(MPRIS_PREFIX))
+        .collect::<Vec<_>>();
+    players.sort();
+    for player in players {
+        if let Some(track) = player_track(connection, &player).await? {
+            return Ok(Some(track));
+        }
+    }
+    Ok(None)
+}
+
+/// Returns the track of `player` if it is playing
+///
+/// Players can vanish between listing and querying them, so a player that can't be read is
+/// treated as not playing.
+async fn player_track(connection: &Connection, player: &str) -> zbus::Result<Option<Track>> {
+    let proxy = PlayerProxy::builder(connection)
+        .destination(player)?
+        .cache_properties(CacheProperties::No)
+        .build()
+        .await?;
+    if proxy.playback_status().await.ok().as_deref() != Some("Playing") {
+        return Ok(None);
+    }
+    let Ok(metadata) = proxy.metadata().await else {
+        return Ok(None);
+    };
+    // not every player reports its position
+    let position = proxy.position().await.ok();
+    Ok(Some(Track::from_metadata(player, &metadata, position)))
+}
+
+/// Track read from the metadata of a MPRIS player
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Track {
+    player: String,
+    title: Option<String>,
+    artists: Vec<String>,
+    album: Option<String>,
+    art_url: Option<String>,
+    length: Option<Duration>,
+    position: Option<Duration>,
+}
+
+impl Track {
+    /// Reads a track from the `Metadata` and `Position` (in microseconds) properties of a player
+    fn from_metadata(
+        player: &str,
+        metadata: &HashMap<String, OwnedValue>,
+        position: Option<i64>,
+    ) -> Self {
+        let string = |key: &str| metadata.get(key).and_then(|value| string_value(value));
+        Self {
+            player: player.to_string(),
+            title: string("xesam:title"),
+            artists: metadata
+                .get("xesam:artist")
+                .map(|value| strings_value(value))
+                .unwrap_or_default(),
+            album: string("xesam:album"),
+            art_url: string("mpris:artUrl"),
+            length: metadata
+                .get("mpris:length")
+                .and_then(|value| micros_value(value))
+                .filter(|length| !length.is_zero()),
+            position: position.map(|position| Duration::from_micros(position.max(0) as u64)),
+        }
+    }
+
+    /// Whether both tracks are the same song on the same player, ignoring the position
+    fn same_track(&self, other: &Track) -> bool {
+        self.player == other.player
+            && self.title == other.title
+            && self.artists == other.artists
+            && self.album == other.album
+            && self.art_url == other.art_url
+            && self.length == other.length
+    }
+
+    /// Unix timestamp in milliseconds at which the track started playing, if the position is known
+    fn start_millis(&self, now: u64) -> Option<u64> {
+        self.position
+            .map(|position| now.saturating_sub(position.as_millis() as u64))
+    }
+
+    /// Builds the listening activity of the track at the unix timestamp `now` in milliseconds
+    ///
+    /// Album art is only shown for http(s) urls since Discord can't read local files. Fields
+    /// shorter than Discord accepts are left out, and so are the timestamps when the player doesn't
+    /// report its position.
+    fn activity(&self, now: u64) -> Result<ActivityRequest, ActivityValidationError> {
+        let timestamps = self.start_millis(now).map(|start| Timestamps {
+            start: Some(start),
+            end: self
+                .length
+                .map(|length| start.saturating_add(length.as_millis() as u64)),
+        });
+        let large_image = self
+            .art_url
+            .clone()
+            .filter(|url| url.starts_with("https://") || url.starts_with("http://"));
+        let large_text = self.album.as_deref().and_then(field);
+        let assets = (large_image.is_some() || large_text.is_some()).then_some(Assets {
+            large_image,
+            large_text,
+            small_image: None,
+            small_text: None,
+        });
+        let state = field(&self.artists.join(", "));
+        Activity::request_builder()
+            .activity_type(ActivityType::Listening)
+            .maybe_timestamps(timestamps)
+            .maybe_details(self.title.as_deref().and_then(field))
+            .maybe_state(state)
+            .maybe_assets(assets)
+            .call()
+    }
+}
+
+/// Truncates `value` to the longest string Discord accepts, or returns [None] if it is too short
+fn field(value: &str) -> Option<String> {
+    let value = value.trim();
+    (value.chars().count() >= MIN_FIELD_LENGTH)
+        .then(|| value.chars().take(MAX_FIELD_LENGTH).collect())
+}
+
+fn string_value(value: &Value<'_>) -> Option<String> {
+    match value {
+        Value::Str(value) if !value.is_empty() => Some(value.to_string()),
+        Value::ObjectPath(value) => Some(value.to_string()),
+        Value::Value(value) => string_value(value),
+        _ => None,
+    }
+}
+
+/// Reads a list of strings, which some players send as a single string instead
+fn strings_value(value: &Value<'_>) -> Vec<String> {
+    match value {
+        Value::Array(values) => values.iter().filter_map(string_value).collect(),
+        Value::Value(value) => strings_value(value),
+        value => string_value(value).into_iter().collect(),
+    }
+}
+
+/// Reads a duration in microseconds, which players send with different integer types
+fn micros_value(value: &Value<'_>) -> Option<Duration> {
+    let micros = match value {
+        Value::I64(micros) => u64::try_from(*micros).ok()?,
+        Value::U64(micros) => *micros,
+        Value::I32(micros) => u64::try_from(*micros).ok()?,
+        Value::U32(micros) => u64::from(*micros),
+        Value::Value(value) => return micros_value(value),
+        _ => return None,
+    };
+    Some(Duration::from_micros(micros))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, time::Duration};
+
+    use pretty_assertions::assert_eq;
+    use zbus::zvariant::{OwnedValue, Value};
+
+    use crate::payload::common::activity::{ActivityType, Assets, Timestamps};
+
+    use super::{Shown, Track, player_track};
+
+    const NOW: u64 = 1_700_000_000_000;
+
+    fn metadata() -> HashMap<String, OwnedValue> {
+        [
+            ("xesam:title", Value::from("Windowlicker")),
+            ("xesam:artist", Value::from(vec!["Aphex Twin"])),
+            ("xesam:album", Value::from("Windowlicker EP")),
+            (
+                "mpris:artUrl",
+                Value::from("https://i.scdn.co/image/windowlicker"),
+            ),
+            ("mpris:length", Value::from(367_000_000_i64)),
+        ]
+        .into_iter()
+        .map(|(key, value)| (key.to_string(), value.try_into().unwrap()))
+        .collect()
+    }
+
+    #[test]
+    fn track_to_listening_activity() {
+        let track = Track::from_metadata(
+            "org.mpris.MediaPlayer2.spotify",
+            &metadata(),
+            Some(7_000_000),
+        );
+        assert_eq!(track.length, Some(Duration::from_secs(367)));
+        let activity = track.activity(NOW).unwrap().into_activity();
+        assert_eq!(activity.activity_type, Some(ActivityType::Listening));
+        assert_eq!(activity.details.as_deref(), Some("Windowlicker"));
+        assert_eq!(activity.state.as_deref(), Some("Aphex Twin"));
+        assert_eq!(
+            activity.assets,
+            Some(Assets {
+                large_image: Some("https://i.scdn.co/image/windowlicker".to_string()),
+                large_text: Some("Windowlicker EP".to_string()),
+                small_image: None,
+                small_text: None,
+            })
+        );
+        assert_eq!(
+            activity.timestamps,
+            Some(Timestamps {
+                start: Some(NOW - 7_000),
+                end: Some(NOW - 7_000 + 367_000),
+            })
+        );
+    }
+
+    #[test]
+    fn track_without_remote_art() {
+        let mut metadata = metadata();
+        metadata.insert(
+            "mpris:artUrl".to_string(),
+            Value::from("file:///tmp/cover.png").try_into().unwrap(),
+        );
+        metadata.remove("xesam:album");
+        metadata.remove("mpris:length");
+        let track = Track::from_metadata("org.mpris.MediaPlayer2.vlc", &metadata, Some(0));
+        let activity = track.activity(NOW).unwrap().into_activity();
+        assert_eq!(activity.assets, None);
+        assert_eq!(
+            activity.timestamps,
+            Some(Timestamps {
+                start: Some(NOW),
+                end: None,
+            })
+        );
+    }
+
+    #[test]
+    fn track_with_short_fields() {
+        let mut metadata = metadata();
+        metadata.insert(
+            "xesam:title".to_string(),
+            Value::from("X").try_into().unwrap(),
+        );
+        metadata.insert(
+            "xesam:artist".to_string(),
+            Value::from(vec!["A"]).try_into().unwrap(),
+        );
+        metadata.insert(
+            "xesam:album".to_string(),
+            Value::from("B").try_into().unwrap(),
+        );
+        let track = Track::from_metadata("org.mpris.MediaPlayer2.spotify", &metadata, Some(0));
+        let activity = track.activity(NOW).unwrap().into_activity();
+        assert_eq!(activity.details, None);
+        assert_eq!(activity.state, None);
+        assert_eq!(
+            activity.assets,
+            Some(Assets {
+                large_image: Some("https://i.scdn.co/image/windowlicker".to_string()),
+                large_text: None,
+                small_image: None,
+                small_text: None,
+            })
+        );
+    }
+
+    #[test]
+    fn track_without_position() {
+        let track = Track::from_metadata("org.mpris.MediaPlayer2.firefox", &metadata(), None);
+        let activity = track.activity(NOW).unwrap().into_activity();
+        assert_eq!(activity.timestamps, None);
+
+        let shown = Shown {
+            start: track.start_millis(NOW),
+            track: track.clone(),
+        };
+        assert!(shown.shows(&track, track.start_millis(NOW + 60_000)));
+    }
+
+    #[test]
+    fn shown_track_moved() {
+        let track = Track::from_metadata("org.mpris.MediaPlayer2.spotify", &metadata(), Some(0));
+        let shown = Shown {
+            start: track.start_millis(NOW),
+            track: track.clone(),
+        };
+        assert!(shown.shows(&track, track.start_millis(NOW + 1_000)));
+        assert!(!shown.shows(&track, track.start_millis(NOW + 60_000)));
+        assert!(!shown.shows(&track, None));
+    }
+
+    struct FakePlayer {
+        status: &'static str,
+        position: Option<i64>,
+    }
+
+    #[zbus::interface(name = "org.mpris.MediaPlayer2.Player")]
+    impl FakePlayer {
+        #[zbus(property)]
+        fn playback_status(&self) -> String {
+            self.status.to_string()
+        }
+
+        #[zbus(property)]
+        fn metadata(&self) -> HashMap<String, OwnedValue> {
+            metadata()
+        }
+
+        #[zbus(property)]
+        fn position(&self) -> zbus::fdo::Result<i64> {
+            self.position.ok_or_else(|| {
+                zbus::fdo::Error::NotSupported("Position is not supported".to_string())
+            })
+        }
+    }
+
+    /// Runs against the session bus, e.g. through
+    /// `dbus-run-session -- cargo test --features mpris -- --ignored`
+    #[tokio::test]
+    #[ignore = "needs a D-Bus session bus"]
+    async fn read_player_from_session_bus() {
+        let player = format!("org.mpris.MediaPlayer2.sdkcord{}", std::process::id());
+        let server = zbus::connection::Builder::session()
+            .unwrap()
+            .name(player.as_str())
+            .unwrap()
+            .serve_at(
+                "/org/mpris/MediaPlayer2",
+                FakePlayer {
+                    status: "Playing",
+                    position: Some(7_000_000),
+                },
+            )
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+        let connection = zbus::Connection::session().await.unwrap();
+        let track = player_track(&connection, &player).await.unwrap();
+        assert_eq!(
+            track,
+            Some(Track::from_metadata(&player, &metadata(), Some(7_000_000)))
+        );
+
+        server
+            .object_server()
+            .remove::<FakePlayer, _>("/org/mpris/MediaPlayer2")
+            .await
+            .unwrap();
+        server
+            .object_server()
+            .at(
+                "/org/mpris/MediaPlayer2",
+                FakePlayer {
+                    status: "Paused",
+                    position: Some(7_000_000),
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(player_track(&connection, &player).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a D-Bus session bus"]
+    async fn read_player_without_position_from_session_bus() {
+        let player = format!("org.mpris.MediaPlayer2.sdkcord{}_nopos", std::process::id());
+        let _server = zbus::connection::Builder::session()
+            .unwrap()
+            .name(player.as_str())
+            .unwrap()
+            .serve_at(
+                "/org/mpris/MediaPlayer2",
+                FakePlayer {
+                    status: "Playing",
+                    position: None,
+                },
+            )
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+        let connection = zbus::Connection::session().await.unwrap();
+        let track = player_track(&connection, &player).await.unwrap();
+        assert_eq!(
+            track,
+            Some(Track::from_metadata(&player, &metadata(), None))
+        );
+    }
+}
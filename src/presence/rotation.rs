@@ -0,0 +1,228 @@
+//! Rotation of the rich presence between several activities.
+use std::{
+    collections::VecDeque,
+    fmt::{self, Debug},
+    sync::Arc,
+    time::Duration,
+};
+
+use tokio::{
+    sync::watch,
+    time::{Instant, sleep, sleep_until},
+};
+use tracing::error;
+
+use super::ActivityTemplate;
+use crate::{
+    client::SdkClient,
+    payload::{
+        SetActivityArgs,
+        common::activity::{ActivityRequest, ActivityValidationError},
+    },
+    task::AbortOnDrop,
+};
+
+/// Number of SET_ACTIVITY requests Discord accepts per [RATE_LIMIT_WINDOW]
+pub(super) const RATE_LIMIT_REQUESTS: usize = 5;
+/// Window of the SET_ACTIVITY rate limit
+pub(super) const RATE_LIMIT_WINDOW: Duration = Duration::from_secs(20);
+/// Shortest duration of a [RotationEntry], so a rotation of entries whose templates keep
+/// failing does not spin
+pub const MIN_ENTRY_DURATION: Duration = Duration::from_secs(1);
+
+/// An activity shown for a fixed duration during a [PresenceRotation]
+#[derive(Clone)]
+pub struct RotationEntry {
+    duration: Duration,
+    template: Arc<ActivityTemplate>,
+}
+
+impl RotationEntry {
+    /// Creates an entry whose activity is rebuilt by `template` every time it is shown, e.g. to
+    /// refresh a viewer count
+    ///
+    /// Durations shorter than [MIN_ENTRY_DURATION] are raised to it.
+    pub fn new(
+        duration: Duration,
+        template: impl Fn() -> Result<ActivityRequest, ActivityValidationError> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            duration: duration.max(MIN_ENTRY_DURATION),
+            template: Arc::new(template),
+        }
+    }
+
+    /// Creates an entry that always shows the same activity
+    pub fn fixed(duration: Duration, activity: ActivityRequest) -> Self {
+        Self::new(duration, move || Ok(activity.clone()))
+    }
+}
+
+impl Debug for RotationEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RotationEntry")
+            .field("duration", &self.duration)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Cycles the rich presence of the current process through a list of [RotationEntry]
+///
+/// An entry may be shown longer than its duration while requests are held back by the rate
+/// limit.
+#[derive(Debug)]
+pub struct PresenceRotation {
+    paused: watch::Sender<bool>,
+    _task: AbortOnDrop,
+}
+
+impl PresenceRotation {
+    /// Starts rotating the activity of the current process
+    pub fn start(client: SdkClient, entries: Vec<RotationEntry>) -> Self {
+        Self::start_with_pid(client, std::process::id(), entries)
+    }
+
+    /// Starts rotating the activity of the process with the given id
+    pub fn start_with_pid(client: SdkClient, pid: u32, entries: Vec<RotationEntry>) -> Self {
+        let (paused, paused_rx) = watch::channel(false);
+        Self {
+            paused,
+            _task: AbortOnDrop::spawn(rotate(client, pid, entries, paused_rx)),
+        }
+    }
+
+    /// Pause the rotation; the current activity stays until the rotation is resumed
+    pub fn pause(&self) {
+        self.paused.send_replace(true);
+    }
+
+    /// Resume a paused rotation
+    pub fn resume(&self) {
+        self.paused.send_replace(false);
+    }
+
+    /// Whether the rotation is paused
+    pub fn is_paused(&self) -> bool {
+        *self.paused.borrow()
+    }
+}
+
+async fn rotate(
+    client: SdkClient,
+    pid: u32,
+    entries: Vec<RotationEntry>,
+    mut paused: watch::Receiver<bool>,
+) {
+    let mut rate_limiter = RateLimiter::new(RATE_LIMIT_REQUESTS, RATE_LIMIT_WINDOW);
+    for entry in entries.iter().cycle() {
+        if paused.wait_for(|paused| !*paused).await.is_err() {
+            return;
+        }
+        let activity = match (entry.template)() {
+            Ok(activity) => activity,
+            Err(err) => {
+                error!("skipping invalid activity in rotation: {}", err);
+                sleep(entry.duration).await;
+                continue;
+            }
+        };
+        rate_limiter.acquire().await;
+        let args = SetActivityArgs::builder()
+            .pid(pid)
+            .activity(Box::new(activity))
+            .build();
+        if let Err(err) = client.set_activity(args).await {
+            error!("failed to set activity in rotation: {}", err);
+        }
+        sleep(entry.duration).await;
+    }
+}
+
+/// Sliding window rate limiter
+#[derive(Debug)]
+pub(super) struct RateLimiter {
+    max: usize,
+    window: Duration,
+    sent: VecDeque<Instant>,
+}
+
+impl RateLimiter {
+    pub(super) const fn new(max: usize, window: Duration) -> Self {
+        Self {
+            max,
+            window,
+            sent: VecDeque::new(),
+        }
+    }
+
+    /// Returns how long to wait at `now` before the next request can be sent
+    pub(super) fn delay(&mut self, now: Instant) -> Option<Duration> {
+        while self
+            .sent
+            .front()
+            .is_some_and(|sent| now.duration_since(*sent) >= self.window)
+        {
+            self.sent.pop_front();
+        }
+        if self.sent.len() < self.max {
+            return None;
+        }
+        self.sent
+            .front()
+            .map(|oldest| self.window - now.duration_since(*oldest))
+    }
+
+    pub(super) fn record(&mut self, now: Instant) {
+        self.sent.push_back(now);
+    }
+
+    /// Waits until the next request can be sent and records it
+    pub(super) async fn acquire(&mut self) {
+        if let Some(delay) = self.delay(Instant::now()) {
+            sleep_until(Instant::now() + delay).await;
+        }
+        self.record(Instant::now());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use pretty_assertions::assert_eq;
+    use tokio::time::Instant;
+
+    use super::{MIN_ENTRY_DURATION, RateLimiter, RotationEntry};
+    use crate::payload::common::activity::ActivityValidationError;
+
+    #[test]
+    fn rate_limiter_delays_requests() {
+        let mut rate_limiter = RateLimiter::new(2, Duration::from_secs(20));
+        let start = Instant::now();
+        assert_eq!(rate_limiter.delay(start), None);
+        rate_limiter.record(start);
+        rate_limiter.record(start + Duration::from_secs(5));
+        assert_eq!(
+            rate_limiter.delay(start + Duration::from_secs(10)),
+            Some(Duration::from_secs(10))
+        );
+        assert_eq!(rate_limiter.delay(start + Duration::from_secs(20)), None);
+        rate_limiter.record(start + Duration::from_secs(20));
+        assert_eq!(
+            rate_limiter.delay(start + Duration::from_secs(21)),
+            Some(Duration::from_secs(4))
+        );
+    }
+
+    #[test]
+    fn entry_duration_has_a_minimum() {
+        let entry = RotationEntry::new(Duration::ZERO, || {
+            Err(ActivityValidationError::TooManyButtons(3))
+        });
+        assert_eq!(entry.duration, MIN_ENTRY_DURATION);
+        let entry = RotationEntry::new(Duration::from_secs(30), || {
+            Err(ActivityValidationError::TooManyButtons(3))
+        });
+        assert_eq!(entry.duration, Duration::from_secs(30));
+    }
+}
@@ -0,0 +1,589 @@
+//! Activities described by a TOML or JSON file with placeholders, reloaded when the file changes.
+//!
+//! ```toml
+//! type = "Playing"
+//! details = "Ranked match on {map}"
+//! state = "In a party ({party_size} of {party_max})"
+//! show_elapsed = true
+//!
+//! [assets]
+//! large_image = "map_{map}"
+//! large_text = "Playing for {elapsed}"
+//!
+//! [party]
+//! id = "party-1"
+//! ```
+//!
+//! Text between braces is replaced when the activity is rendered: `{elapsed}` by the time since
+//! [TemplateContext::started_at], `{party_size}` and `{party_max}` by the party set with
+//! [TemplateContext::set_party] and anything else by the variables set with
+//! [TemplateContext::set_variable]. `{{` is a literal brace.
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use serde::{Deserialize, Serialize};
+use serde_with::{DisplayFromStr, PickFirst, serde_as, skip_serializing_none};
+use thiserror::Error;
+use tokio::{sync::watch, time::sleep};
+use tracing::error;
+
+use super::rotation::{RATE_LIMIT_REQUESTS, RATE_LIMIT_WINDOW, RateLimiter};
+use crate::{
+    client::SdkClient,
+    payload::{
+        SetActivityArgs,
+        common::activity::{
+            Activity, ActivityRequest, ActivityType, ActivityValidationError, Assets, Button,
+            Party, StatusDisplayType, Timestamps, unix_millis,
+        },
+    },
+    task::AbortOnDrop,
+};
+
+/// Activity read from a presence file
+///
+/// `type` and `status_display_type` are either the name of the variant, e.g. `"Listening"`, or
+/// its number. The size of the `party` is replaced by the party of the [TemplateContext] if one
+/// is set, which is also shown when the template has no `party`.
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct PresenceTemplate {
+    #[serde(rename = "type")]
+    #[serde_as(as = "PickFirst<(DisplayFromStr, _)>")]
+    pub activity_type: ActivityType,
+    pub name: Option<String>,
+    pub details: Option<String>,
+    pub details_url: Option<String>,
+    pub state: Option<String>,
+    pub state_url: Option<String>,
+    #[serde_as(as = "Option<PickFirst<(DisplayFromStr, _)>>")]
+    pub status_display_type: Option<StatusDisplayType>,
+    pub assets: Option<Assets>,
+    pub party: Option<Party>,
+    pub buttons: Option<Vec<Button>>,
+    /// Show the time elapsed since [TemplateContext::started_at]
+    #[serde(default)]
+    pub show_elapsed: bool,
+}
+
+impl PresenceTemplate {
+    /// Reads a template from a `.toml` or `.json` file
+    ///
+    /// # Errors
+    /// A [TemplateError] is returned if the file can't be read, has another extension or
+    /// doesn't hold a valid template
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, TemplateError> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)?;
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("toml") => Self::from_toml(&contents),
+            Some("json") => Self::from_json(&contents),
+            _ => Err(TemplateError::UnsupportedFormat(path.to_path_buf())),
+        }
+    }
+
+    /// Parses a template from TOML
+    ///
+    /// # Errors
+    /// A [TemplateError] is returned if `toml` doesn't hold a valid template
+    pub fn from_toml(toml: &str) -> Result<Self, TemplateError> {
+        Ok(toml::from_str(toml)?)
+    }
+
+    /// Parses a template from JSON
+    ///
+    /// # Errors
+    /// A [TemplateError] is returned if `json` doesn't hold a valid template
+    pub fn from_json(json: &str) -> Result<Self, TemplateError> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Builds the activity by replacing the placeholders with the values of `context`
+    ///
+    /// # Errors
+    /// A [TemplateError] is returned if a placeholder has no value or the activity is invalid
+    pub fn render(&self, context: &TemplateContext) -> Result<ActivityRequest, TemplateError> {
+        self.render_at(context, SystemTime::now())
+    }
+
+    fn render_at(
+        &self,
+        context: &TemplateContext,
+        now: SystemTime,
+    ) -> Result<ActivityRequest, TemplateError> {
+        let render = |value: &Option<String>| {
+            value
+                .as_deref()
+                .map(|value| context.substitute(value, now))
+                .transpose()
+        };
+        let assets = self
+            .assets
+            .as_ref()
+            .map(|assets| {
+                Ok::<_, TemplateError>(Assets {
+                    large_image: render(&assets.large_image)?,
+                    large_text: render(&assets.large_text)?,
+                    small_image: render(&assets.small_image)?,
+                    small_text: render(&assets.small_text)?,
+                })
+            })
+            .transpose()?;
+        let buttons = self
+            .buttons
+            .as_ref()
+            .map(|buttons| {
+                buttons
+                    .iter()
+                    .map(|button| {
+                        Ok(Button {
+                            label: render(&button.label)?,
+                            url: render(&button.url)?,
+                        })
+                    })
+                    .collect::<Result<Vec<_>, TemplateError>>()
+            })
+            .transpose()?;
+        let party = match &self.party {
+            Some(party) => Some(Party {
+                id: party.id.clone(),
+                size: context.party.or(party.size),
+            }),
+            None => context.party.map(|size| Party {
+                id: None,
+                size: Some(size),
+            }),
+        };
+        let timestamps = self.show_elapsed.then(|| Timestamps {
+            start: Some(unix_millis(context.started_at)),
+            end: None,
+        });
+        Ok(Activity::request_builder()
+            .activity_type(self.activity_type)
+            .maybe_name(render(&self.name)?)
+            .maybe_details(render(&self.details)?)
+            .maybe_details_url(render(&self.details_url)?)
+            .maybe_state(render(&self.state)?)
+            .maybe_state_url(render(&self.state_url)?)
+            .maybe_status_display_type(self.status_display_type)
+            .maybe_assets(assets)
+            .maybe_party(party)
+            .maybe_buttons(buttons)
+            .maybe_timestamps(timestamps)
+            .call()?)
+    }
+
+    /// Whether a text of the template shows `{elapsed}`, which changes while time passes
+    fn uses_elapsed(&self) -> bool {
+        let assets = self.assets.iter().flat_map(|assets| {
+            [
+                &assets.large_image,
+                &assets.large_text,
+                &assets.small_image,
+                &assets.small_text,
+            ]
+        });
+        let buttons = self
+            .buttons
+            .iter()
+            .flatten()
+            .flat_map(|button| [&button.label, &button.url]);
+        [
+            &self.name,
+            &self.details,
+            &self.details_url,
+            &self.state,
+            &self.state_url,
+        ]
+        .into_iter()
+        .chain(assets)
+        .chain(buttons)
+        .flatten()
+        .any(|text| text.contains("{elapsed}"))
+    }
+}
+
+/// Values of the placeholders of a [PresenceTemplate]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateContext {
+    started_at: SystemTime,
+    party: Option<[u32; 2]>,
+    variables: HashMap<String, String>,
+}
+
+impl Default for TemplateContext {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TemplateContext {
+    /// Creates a context started now without a party or variables
+    pub fn new() -> Self {
+        Self {
+            started_at: SystemTime::now(),
+            party: None,
+            variables: HashMap::new(),
+        }
+    }
+
+    /// Returns the time `{elapsed}` and the elapsed timestamp count from
+    pub const fn started_at(&self) -> SystemTime {
+        self.started_at
+    }
+
+    /// Set the time `{elapsed}` and the elapsed timestamp count from, e.g. the start of a match
+    pub const fn set_started_at(&mut self, started_at: SystemTime) {
+        self.started_at = started_at;
+    }
+
+    /// Set the current and max size of the party, also used as the size of the party of the
+    /// activity
+    pub const fn set_party(&mut self, current: u32, max: u32) {
+        self.party = Some([current, max]);
+    }
+
+    /// Remove the party
+    pub const fn clear_party(&mut self) {
+        self.party = None;
+    }
+
+    /// Set the value of the `{name}` placeholder
+    pub fn set_variable(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        self.variables.insert(name.into(), value.into());
+    }
+
+    /// Remove the value of the `{name}` placeholder
+    pub fn remove_variable(&mut self, name: &str) {
+        self.variables.remove(name);
+    }
+
+    fn value(&self, name: &str, now: SystemTime) -> Option<String> {
+        match name {
+            "elapsed" => Some(format_elapsed(
+                now.duration_since(self.started_at).unwrap_or_default(),
+            )),
+            "party_size" => self.party.map(|[current, _]| current.to_string()),
+            "party_max" => self.party.map(|[_, max]| max.to_string()),
+            name => self.variables.get(name).cloned(),
+        }
+    }
+
+    fn substitute(&self, text: &str, now: SystemTime) -> Result<String, TemplateError> {
+        let mut rendered = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(start) = rest.find('{') {
+            rendered.push_str(&rest[..start]);
+            rest = &rest[start + 1..];
+            if let Some(escaped) = rest.strip_prefix('{') {
+                rendered.push('{');
+                rest = escaped;
+                continue;
+            }
+            let end = rest
+                .find('}')
+                .ok_or_else(|| TemplateError::UnclosedPlaceholder(text.to_string()))?;
+            let name = &rest[..end];
+            let value = self
+                .value(name, now)
+                .ok_or_else(|| TemplateError::UnknownPlaceholder(name.to_string()))?;
+            rendered.push_str(&value);
+            rest = &rest[end + 1..];
+        }
+        rendered.push_str(rest);
+        Ok(rendered)
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum TemplateError {
+    #[error("failed to read presence file: {0}")]
+    Io(#[from] io::Error),
+    #[error("invalid TOML presence file: {0}")]
+    Toml(#[from] toml::de::Error),
+    #[error("invalid JSON presence file: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("presence file {0} is neither a .toml nor a .json file")]
+    UnsupportedFormat(PathBuf),
+    #[error("placeholder {{{0}}} has no value")]
+    UnknownPlaceholder(String),
+    #[error("placeholder is not closed in \"{0}\"")]
+    UnclosedPlaceholder(String),
+    #[error(transparent)]
+    Validation(#[from] ActivityValidationError),
+}
+
+/// Sets the activity of the current process from a presence file
+///
+/// The modification time of the file is checked on every interval and the activity is sent
+/// again when the file or the [TemplateContext] changed. Templates showing `{elapsed}` are also
+/// rendered on every interval and sent when the rendered activity changed.
+#[derive(Debug)]
+pub struct PresenceFileWatcher {
+    context: watch::Sender<TemplateContext>,
+    _task: AbortOnDrop,
+}
+
+impl PresenceFileWatcher {
+    /// Starts watching the `.toml` or `.json` file at `path`
+    pub fn start(
+        client: SdkClient,
+        path: impl Into<PathBuf>,
+        context: TemplateContext,
+        interval: Duration,
+    ) -> Self {
+        let (context, context_rx) = watch::channel(context);
+        let task = watch_file(
+            client,
+            std::process::id(),
+            path.into(),
+            context_rx,
+            interval,
+        );
+        Self {
+            context,
+            _task: AbortOnDrop::spawn(task),
+        }
+    }
+
+    /// Update the values of the placeholders and send the activity again
+    pub fn update_context(&self, update: impl FnOnce(&mut TemplateContext)) {
+        self.context.send_modify(update);
+    }
+}
+
+async fn watch_file(
+    client: SdkClient,
+    pid: u32,
+    path: PathBuf,
+    mut context: watch::Receiver<TemplateContext>,
+    interval: Duration,
+) {
+    let mut rate_limiter = RateLimiter::new(RATE_LIMIT_REQUESTS, RATE_LIMIT_WINDOW);
+    let mut modified: Option<SystemTime> = None;
+    let mut template: Option<PresenceTemplate> = None;
+    let mut last_sent: Option<ActivityRequest> = None;
+    let mut unreadable = false;
+    let mut changed = false;
+    loop {
+        let reload_path = path.clone();
+        let reloaded = tokio::task::spawn_blocking(move || reload(&reload_path, modified))
+            .await
+            .unwrap_or_else(|err| Err(io::Error::other(err)));
+        match reloaded {
+            Ok(Some((file_modified, loaded))) => {
+                modified = Some(file_modified);
+                unreadable = false;
+                match loaded {
+                    Ok(loaded) => {
+                        template = Some(loaded);
+                        changed = true;
+                    }
+                    Err(err) => error!("invalid presence file {}: {}", path.display(), err),
+                }
+            }
+            Ok(None) => unreadable = false,
+            Err(err) => {
+                if !unreadable {
+                    error!("failed to read presence file {}: {}", path.display(), err);
+                }
+                unreadable = true;
+            }
+        }
+        if let Some(template) = &template
+            && (changed || template.uses_elapsed())
+        {
+            let rendered = template.render(&context.borrow_and_update());
+            match rendered {
+                Ok(activity) if last_sent.as_ref() != Some(&activity) => {
+                    rate_limiter.acquire().await;
+                    let args = SetActivityArgs::builder()
+                        .pid(pid)
+                        .activity(Box::new(activity.clone()))
+                        .build();
+                    match client.set_activity(args).await {
+                        Ok(_) => last_sent = Some(activity),
+                        Err(err) => error!("failed to set activity from presence file: {}", err),
+                    }
+                }
+                Ok(_) => {}
+                // the same error would be logged on every interval otherwise
+                Err(err) if changed => {
+                    error!("failed to render presence file {}: {}", path.display(), err);
+                }
+                Err(_) => {}
+            }
+            changed = false;
+        }
+        tokio::select! {
+            _ = sleep(interval) => {}
+            context_changed = context.changed() => {
+                if context_changed.is_err() {
+                    return;
+                }
+                changed = true;
+            }
+        }
+    }
+}
+
+/// Loads the template at `path` if it was modified since `last_modified`
+fn reload(
+    path: &Path,
+    last_modified: Option<SystemTime>,
+) -> io::Result<Option<(SystemTime, Result<PresenceTemplate, TemplateError>)>> {
+    let modified = fs::metadata(path)?.modified()?;
+    if last_modified == Some(modified) {
+        return Ok(None);
+    }
+    Ok(Some((modified, PresenceTemplate::load(path))))
+}
+
+/// Formats a duration like a timer, e.g. `4:05` or `1:02:03`
+fn format_elapsed(elapsed: Duration) -> String {
+    let seconds = elapsed.as_secs();
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if hours > 0 {
+        format!("{hours}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{minutes}:{seconds:02}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use pretty_assertions::assert_eq;
+
+    use crate::payload::common::activity::{ActivityType, Assets, Party, Timestamps};
+
+    use super::{PresenceTemplate, TemplateContext, TemplateError};
+    #[cfg(unix)]
+    use {super::PresenceFileWatcher, crate::test_util::mock_client, serde_json::json, std::fs};
+
+    const TEMPLATE: &str = r#"
+        type = "Playing"
+        details = "Ranked match on {map}"
+        state = "In a party ({party_size} of {party_max})"
+        show_elapsed = true
+
+        [assets]
+        large_image = "map_{map}"
+        large_text = "Playing for {elapsed} {{no placeholder}"
+
+        [party]
+        id = "party-1"
+    "#;
+
+    fn context() -> TemplateContext {
+        let mut context = TemplateContext::new();
+        context.set_started_at(UNIX_EPOCH + Duration::from_secs(1_700_000_000));
+        context.set_party(2, 4);
+        context.set_variable("map", "dust2");
+        context
+    }
+
+    #[test]
+    fn render_toml_template() {
+        let template = PresenceTemplate::from_toml(TEMPLATE).unwrap();
+        assert!(template.uses_elapsed());
+        let now = context().started_at() + Duration::from_secs(3723);
+        let activity = template.render_at(&context(), now).unwrap().into_activity();
+        assert_eq!(activity.activity_type, Some(ActivityType::Playing));
+        assert_eq!(activity.details.as_deref(), Some("Ranked match on dust2"));
+        assert_eq!(activity.state.as_deref(), Some("In a party (2 of 4)"));
+        assert_eq!(
+            activity.assets,
+            Some(Assets {
+                large_image: Some("map_dust2".to_string()),
+                large_text: Some("Playing for 1:02:03 {no placeholder}".to_string()),
+                small_image: None,
+                small_text: None,
+            })
+        );
+        assert_eq!(
+            activity.party,
+            Some(Party {
+                id: Some("party-1".to_string()),
+                size: Some([2, 4]),
+            })
+        );
+        assert_eq!(
+            activity.timestamps,
+            Some(Timestamps {
+                start: Some(1_700_000_000_000),
+                end: None,
+            })
+        );
+    }
+
+    #[test]
+    fn render_json_template() {
+        let template = PresenceTemplate::from_json(
+            r#"{"type": 2, "details": "{song}", "status_display_type": "Details"}"#,
+        )
+        .unwrap();
+        assert!(!template.uses_elapsed());
+        let mut context = context();
+        assert!(matches!(
+            template.render(&context),
+            Err(TemplateError::UnknownPlaceholder(name)) if name == "song"
+        ));
+        context.set_variable("song", "Windowlicker");
+        let activity = template.render(&context).unwrap().into_activity();
+        assert_eq!(activity.activity_type, Some(ActivityType::Listening));
+        assert_eq!(activity.details.as_deref(), Some("Windowlicker"));
+        assert_eq!(activity.timestamps, None);
+        assert_eq!(
+            activity.party,
+            Some(Party {
+                id: None,
+                size: Some([2, 4]),
+            })
+        );
+        assert!(matches!(
+            PresenceTemplate::from_json(r#"{"type": "Playing", "detail": "typo"}"#),
+            Err(TemplateError::Json(_))
+        ));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn resend_elapsed_text_over_time() {
+        let (client, mut discord) = mock_client().await;
+        let path =
+            std::env::temp_dir().join(format!("sdkcord-presence-{}.toml", std::process::id()));
+        fs::write(
+            &path,
+            "type = \"Playing\"\ndetails = \"Playing for {elapsed}\"\n",
+        )
+        .unwrap();
+        let _watcher = PresenceFileWatcher::start(
+            client,
+            path.clone(),
+            TemplateContext::new(),
+            Duration::from_millis(50),
+        );
+        let mut details = Vec::new();
+        for _ in 0..2 {
+            let request = discord.recv().await;
+            assert_eq!(request["cmd"], "SET_ACTIVITY");
+            details.push(request["args"]["activity"]["details"].clone());
+            discord
+                .respond(&request, request["args"]["activity"].clone())
+                .await;
+        }
+        assert_eq!(
+            details,
+            [json!("Playing for 0:00"), json!("Playing for 0:01")]
+        );
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,314 @@
+//! Registration of the `discord-<client_id>://` URI scheme used by Discord to launch the
+//! application, e.g. when a user accepts an invite to join a game through [Secrets::join].
+//!
+//! On Linux the scheme is handled by a [XDG desktop entry](https://specifications.freedesktop.org/desktop-entry-spec/latest/)
+//! written to `$XDG_DATA_HOME/applications`, which is made the default handler of the
+//! `x-scheme-handler/discord-<client_id>` MIME type in `$XDG_CONFIG_HOME/mimeapps.list` through
+//! `xdg-mime`, or by editing the file directly if `xdg-mime` is not installed.
+//!
+//! ```no_run
+//! use sdkcord::register::register_application;
+//!
+//! register_application("1234567890", "/usr/bin/my-game --discord").unwrap();
+//! ```
+//!
+//! [Secrets::join]: crate::payload::common::activity::Secrets::join
+use std::{
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use thiserror::Error;
+
+/// Section of `mimeapps.list` holding the default handler of each MIME type
+const DEFAULT_APPLICATIONS: &str = "[Default Applications]";
+
+/// Registers `command` as the handler of `discord-<client_id>://` links
+///
+/// `command` is the `Exec` line of the desktop entry, so arguments containing spaces have to be
+/// quoted as described by the desktop entry specification. The link is passed as the last
+/// argument. Registering an application again replaces the previous command.
+///
+/// # Errors
+/// A [RegisterError] is returned if `client_id` is not a valid application id, the XDG
+/// directories can't be found or the files can't be written
+pub fn register_application(client_id: &str, command: &str) -> Result<(), RegisterError> {
+    Registrar::from_env()?.register(client_id, command)
+}
+
+/// Removes the handler of `discord-<client_id>://` links added by [register_application]
+///
+/// # Errors
+/// A [RegisterError] is returned if `client_id` is not a valid application id, the XDG
+/// directories can't be found or the files can't be written
+pub fn unregister_application(client_id: &str) -> Result<(), RegisterError> {
+    Registrar::from_env()?.unregister(client_id)
+}
+
+#[derive(Debug, Error)]
+pub enum RegisterError {
+    #[error("client id {0} is not a valid application id")]
+    InvalidClientId(String),
+    #[error("could not find the home directory of the user")]
+    NoHomeDirectory,
+    #[error("failed to write the URI scheme handler: {0}")]
+    Io(#[from] io::Error),
+}
+
+/// Locations of the files registering the URI scheme
+#[derive(Debug)]
+struct Registrar {
+    applications: PathBuf,
+    mimeapps: PathBuf,
+    /// Whether `xdg-mime` and `update-desktop-database` are run on the host
+    host_tools: bool,
+}
+
+impl Registrar {
+    fn from_env() -> Result<Self, RegisterError> {
+        let data_dir = dirs::data_dir().ok_or(RegisterError::NoHomeDirectory)?;
+        let config_dir = dirs::config_dir().ok_or(RegisterError::NoHomeDirectory)?;
+        Ok(Self {
+            applications: data_dir.join("applications"),
+            mimeapps: config_dir.join("mimeapps.list"),
+            host_tools: true,
+        })
+    }
+
+    fn register(&self, client_id: &str, command: &str) -> Result<(), RegisterError> {
+        let scheme = scheme(client_id)?;
+        let desktop_file = format!("{scheme}.desktop");
+        let mime_type = mime_type(&scheme);
+        fs::create_dir_all(&self.applications)?;
+        write_atomic(
+            &self.applications.join(&desktop_file),
+            &desktop_entry(client_id, &scheme, command),
+        )?;
+        if !(self.host_tools && self.xdg_mime_default(&desktop_file, &mime_type)) {
+            let mimeapps = read_mimeapps(&self.mimeapps)?;
+            let mimeapps = set_default_handler(&mimeapps, &mime_type, &desktop_file);
+            if let Some(dir) = self.mimeapps.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            write_atomic(&self.mimeapps, &mimeapps)?;
+        }
+        self.update_desktop_database();
+        Ok(())
+    }
+
+    fn unregister(&self, client_id: &str) -> Result<(), RegisterError> {
+        let scheme = scheme(client_id)?;
+        match fs::remove_file(self.applications.join(format!("{scheme}.desktop"))) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err.into()),
+            _ => {}
+        }
+        if self.mimeapps.exists() {
+            let mimeapps = read_mimeapps(&self.mimeapps)?;
+            write_atomic(
+                &self.mimeapps,
+                &remove_handler(&mimeapps, &mime_type(&scheme)),
+            )?;
+        }
+        self.update_desktop_database();
+        Ok(())
+    }
+
+    /// Sets the default handler with `xdg-mime`, pointed at the same directories as the
+    /// registrar; returns `false` if `xdg-mime` is missing or fails
+    fn xdg_mime_default(&self, desktop_file: &str, mime_type: &str) -> bool {
+        let mut command = Command::new("xdg-mime");
+        command.args(["default", desktop_file, mime_type]);
+        if let Some(data_dir) = self.applications.parent() {
+            command.env("XDG_DATA_HOME", data_dir);
+        }
+        if let Some(config_dir) = self.mimeapps.parent() {
+            command.env("XDG_CONFIG_HOME", config_dir);
+        }
+        command.status().is_ok_and(|status| status.success())
+    }
+
+    /// Refreshes the MIME type cache of the desktop entries; the cache is optional, so a missing
+    /// `update-desktop-database` is not an error
+    fn update_desktop_database(&self) {
+        if self.host_tools {
+            let _ = Command::new("update-desktop-database")
+                .arg(&self.applications)
+                .status();
+        }
+    }
+}
+
+fn scheme(client_id: &str) -> Result<String, RegisterError> {
+    if client_id.is_empty() || !client_id.bytes().all(|byte| byte.is_ascii_digit()) {
+        return Err(RegisterError::InvalidClientId(client_id.to_string()));
+    }
+    Ok(format!("discord-{client_id}"))
+}
+
+fn mime_type(scheme: &str) -> String {
+    format!("x-scheme-handler/{scheme}")
+}
+
+fn desktop_entry(client_id: &str, scheme: &str, command: &str) -> String {
+    // `%` starts a field code in `Exec`, so literal ones have to be doubled
+    let command = command.replace('%', "%%");
+    format!(
+        "[Desktop Entry]\n\
+         Name=Game {client_id}\n\
+         Exec={command} %u\n\
+         Type=Application\n\
+         NoDisplay=true\n\
+         Categories=Discord;Games;\n\
+         MimeType={};\n",
+        mime_type(scheme)
+    )
+}
+
+/// Replaces the file at `path` through a temporary file, so readers never see a partial write
+fn write_atomic(path: &Path, contents: &str) -> io::Result<()> {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".sdkcord-tmp");
+    let tmp = PathBuf::from(tmp);
+    let mut file = fs::File::create(&tmp)?;
+    let result = file
+        .write_all(contents.as_bytes())
+        .and_then(|_| file.sync_all())
+        .and_then(|_| fs::rename(&tmp, path));
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp);
+    }
+    result
+}
+
+fn read_mimeapps(path: &Path) -> io::Result<String> {
+    match fs::read_to_string(path) {
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(String::new()),
+        result => result,
+    }
+}
+
+/// Sets `desktop_file` as the default handler of `mime_type` in the contents of a
+/// `mimeapps.list`, keeping every other entry
+fn set_default_handler(mimeapps: &str, mime_type: &str, desktop_file: &str) -> String {
+    let entry = format!("{mime_type}={desktop_file}");
+    let mut lines = remove_handler(mimeapps, mime_type)
+        .lines()
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    match lines
+        .iter()
+        .position(|line| line.trim() == DEFAULT_APPLICATIONS)
+    {
+        Some(section) => lines.insert(section + 1, entry),
+        None => {
+            if lines.last().is_some_and(|line| !line.trim().is_empty()) {
+                lines.push(String::new());
+            }
+            lines.push(DEFAULT_APPLICATIONS.to_string());
+            lines.push(entry);
+        }
+    }
+    lines.join("\n") + "\n"
+}
+
+/// Removes the handlers of `mime_type` from every section of a `mimeapps.list`
+fn remove_handler(mimeapps: &str, mime_type: &str) -> String {
+    mimeapps
+        .lines()
+        .filter(|line| {
+            line.split_once('=')
+                .is_none_or(|(key, _)| key.trim() != mime_type)
+        })
+        .fold(String::new(), |mut contents, line| {
+            contents.push_str(line);
+            contents.push('\n');
+            contents
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use pretty_assertions::assert_eq;
+
+    use super::{RegisterError, Registrar, set_default_handler};
+
+    #[test]
+    fn set_default_handler_in_existing_section() {
+        let mimeapps = "[Added Associations]\n\
+                        text/html=firefox.desktop\n\
+                        \n\
+                        [Default Applications]\n\
+                        x-scheme-handler/discord-42=old.desktop\n\
+                        text/html=firefox.desktop\n";
+        assert_eq!(
+            set_default_handler(
+                mimeapps,
+                "x-scheme-handler/discord-42",
+                "discord-42.desktop"
+            ),
+            "[Added Associations]\n\
+             text/html=firefox.desktop\n\
+             \n\
+             [Default Applications]\n\
+             x-scheme-handler/discord-42=discord-42.desktop\n\
+             text/html=firefox.desktop\n"
+        );
+        assert_eq!(
+            set_default_handler("", "x-scheme-handler/discord-42", "discord-42.desktop"),
+            "[Default Applications]\n\
+             x-scheme-handler/discord-42=discord-42.desktop\n"
+        );
+    }
+
+    #[test]
+    fn register_and_unregister_application() {
+        let root = std::env::temp_dir().join(format!("sdkcord-register-{}", std::process::id()));
+        let registrar = Registrar {
+            applications: root.join("share/applications"),
+            mimeapps: root.join("config/mimeapps.list"),
+            host_tools: false,
+        };
+        registrar
+            .register("1234567890", "\"/opt/my game/run\" --volume=100%")
+            .unwrap();
+        let desktop_file = registrar.applications.join("discord-1234567890.desktop");
+        assert_eq!(
+            fs::read_to_string(&desktop_file).unwrap(),
+            "[Desktop Entry]\n\
+             Name=Game 1234567890\n\
+             Exec=\"/opt/my game/run\" --volume=100%% %u\n\
+             Type=Application\n\
+             NoDisplay=true\n\
+             Categories=Discord;Games;\n\
+             MimeType=x-scheme-handler/discord-1234567890;\n"
+        );
+        assert_eq!(
+            fs::read_to_string(&registrar.mimeapps).unwrap(),
+            "[Default Applications]\n\
+             x-scheme-handler/discord-1234567890=discord-1234567890.desktop\n"
+        );
+
+        assert!(
+            !root
+                .join("config/mimeapps.list.sdkcord-tmp")
+                .try_exists()
+                .unwrap()
+        );
+
+        registrar.unregister("1234567890").unwrap();
+        assert!(!desktop_file.exists());
+        assert_eq!(
+            fs::read_to_string(&registrar.mimeapps).unwrap(),
+            "[Default Applications]\n"
+        );
+        assert!(matches!(
+            registrar.register("../1234", "true"),
+            Err(RegisterError::InvalidClientId(_))
+        ));
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
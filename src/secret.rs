@@ -0,0 +1,230 @@
+//! Signed [join and spectate secrets][Secrets] carrying a typed payload.
+//!
+//! Discord hands the secret of an activity back verbatim in [ActivityJoinData] and
+//! [ActivitySpectateData], so anything the game needs to connect, e.g. a lobby id and its
+//! password, can travel inside of it. A [SecretSigner] serializes the payload together with an
+//! expiry and signs it with HMAC-SHA256, so secrets that were tampered with or have expired are
+//! rejected when they come back.
+//!
+//! ```
+//! use std::time::Duration;
+//!
+//! use sdkcord::secret::SecretSigner;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Debug, PartialEq, Serialize, Deserialize)]
+//! struct Lobby {
+//!     id: u64,
+//!     password: String,
+//! }
+//!
+//! let signer = SecretSigner::new(b"a key shared by every instance of the game");
+//! let lobby = Lobby {
+//!     id: 42,
+//!     password: "hunter2".to_string(),
+//! };
+//! let secret = signer.sign(&lobby, Duration::from_secs(60 * 60)).unwrap();
+//! assert_eq!(signer.verify::<Lobby>(&secret).unwrap(), lobby);
+//! ```
+//!
+//! [Secrets]: crate::payload::common::activity::Secrets
+//! [ActivityJoinData]: crate::payload::ActivityJoinData
+//! [ActivitySpectateData]: crate::payload::ActivitySpectateData
+use std::{
+    fmt::{self, Debug},
+    time::Duration,
+};
+
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::{DateTime, TimeDelta, Utc};
+use ring::hmac;
+use serde::{Serialize, de::DeserializeOwned};
+use subtle::ConstantTimeEq;
+use thiserror::Error;
+
+/// Longest secret Discord accepts
+pub const MAX_SECRET_LENGTH: usize = 128;
+/// Length in bytes the HMAC-SHA256 tag is truncated to, leaving room for the payload within
+/// [MAX_SECRET_LENGTH]
+const TAG_LENGTH: usize = 16;
+
+/// Signs and verifies secrets with a key shared by every instance of the game
+///
+/// A secret is the url safe base64 encoded JSON of the expiry and payload, followed by a `.` and
+/// the truncated HMAC-SHA256 tag of the encoded part.
+pub struct SecretSigner {
+    key: hmac::Key,
+}
+
+impl SecretSigner {
+    /// Creates a signer from a secret key, which should be at least 32 random bytes
+    pub fn new(key: &[u8]) -> Self {
+        Self {
+            key: hmac::Key::new(hmac::HMAC_SHA256, key),
+        }
+    }
+
+    /// Signs `payload` into a secret that expires after `ttl`
+    ///
+    /// # Errors
+    /// A [SecretError] is returned if the payload can't be serialized or the secret is longer
+    /// than [MAX_SECRET_LENGTH]
+    pub fn sign<T: Serialize>(&self, payload: &T, ttl: Duration) -> Result<String, SecretError> {
+        let ttl = TimeDelta::from_std(ttl).unwrap_or(TimeDelta::MAX);
+        let expires_at = Utc::now()
+            .checked_add_signed(ttl)
+            .unwrap_or(DateTime::<Utc>::MAX_UTC);
+        self.sign_until(payload, expires_at)
+    }
+
+    /// Signs `payload` into a secret that expires at `expires_at`
+    ///
+    /// # Errors
+    /// A [SecretError] is returned if the payload can't be serialized or the secret is longer
+    /// than [MAX_SECRET_LENGTH]
+    pub fn sign_until<T: Serialize>(
+        &self,
+        payload: &T,
+        expires_at: DateTime<Utc>,
+    ) -> Result<String, SecretError> {
+        let body = serde_json::to_vec(&(expires_at.timestamp(), payload))
+            .map_err(SecretError::Serialize)?;
+        let body = URL_SAFE_NO_PAD.encode(body);
+        let tag = URL_SAFE_NO_PAD.encode(self.tag(&body));
+        let secret = format!("{body}.{tag}");
+        if secret.len() > MAX_SECRET_LENGTH {
+            return Err(SecretError::TooLong(secret.len()));
+        }
+        Ok(secret)
+    }
+
+    /// Verifies a secret created by [SecretSigner::sign] and decodes its payload
+    ///
+    /// # Errors
+    /// A [SecretError] is returned if the secret is malformed, was not signed with the key of
+    /// this signer, has expired or holds a different payload type
+    pub fn verify<T: DeserializeOwned>(&self, secret: &str) -> Result<T, SecretError> {
+        self.verify_at(secret, Utc::now())
+    }
+
+    fn verify_at<T: DeserializeOwned>(
+        &self,
+        secret: &str,
+        now: DateTime<Utc>,
+    ) -> Result<T, SecretError> {
+        let (body, tag) = secret.split_once('.').ok_or(SecretError::Malformed)?;
+        let tag = URL_SAFE_NO_PAD
+            .decode(tag)
+            .map_err(|_| SecretError::Malformed)?;
+        if !bool::from(tag.ct_eq(&self.tag(body))) {
+            return Err(SecretError::InvalidSignature);
+        }
+        let body = URL_SAFE_NO_PAD
+            .decode(body)
+            .map_err(|_| SecretError::Malformed)?;
+        let (expires_at, payload) =
+            serde_json::from_slice::<(i64, T)>(&body).map_err(SecretError::Deserialize)?;
+        let expires_at = DateTime::from_timestamp(expires_at, 0).ok_or(SecretError::Malformed)?;
+        if expires_at <= now {
+            return Err(SecretError::Expired(expires_at));
+        }
+        Ok(payload)
+    }
+
+    fn tag(&self, body: &str) -> [u8; TAG_LENGTH] {
+        let tag = hmac::sign(&self.key, body.as_bytes());
+        let mut truncated = [0; TAG_LENGTH];
+        truncated.copy_from_slice(&tag.as_ref()[..TAG_LENGTH]);
+        truncated
+    }
+}
+
+impl Debug for SecretSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SecretSigner").finish_non_exhaustive()
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum SecretError {
+    #[error("failed to serialize the secret payload: {0}")]
+    Serialize(#[source] serde_json::Error),
+    #[error("failed to deserialize the secret payload: {0}")]
+    Deserialize(#[source] serde_json::Error),
+    #[error("secret is {0} characters long but Discord accepts at most {MAX_SECRET_LENGTH}")]
+    TooLong(usize),
+    #[error("secret is malformed")]
+    Malformed,
+    #[error("secret signature is invalid")]
+    InvalidSignature,
+    #[error("secret expired at {0}")]
+    Expired(DateTime<Utc>),
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, TimeDelta};
+    use pretty_assertions::assert_eq;
+    use serde::{Deserialize, Serialize};
+
+    use super::{SecretError, SecretSigner};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Lobby {
+        id: String,
+        password: String,
+    }
+
+    fn lobby() -> Lobby {
+        Lobby {
+            id: "1234567890123456789".to_string(),
+            password: "hunter2".to_string(),
+        }
+    }
+
+    #[test]
+    fn sign_and_verify_secret() {
+        let signer = SecretSigner::new(b"0123456789abcdef0123456789abcdef");
+        let expires_at = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let secret = signer.sign_until(&lobby(), expires_at).unwrap();
+        assert!(secret.len() <= 128);
+
+        let before = expires_at - TimeDelta::seconds(1);
+        assert_eq!(signer.verify_at::<Lobby>(&secret, before).unwrap(), lobby());
+        assert!(matches!(
+            signer.verify_at::<Lobby>(&secret, expires_at),
+            Err(SecretError::Expired(expired_at)) if expired_at == expires_at
+        ));
+        assert!(matches!(
+            SecretSigner::new(b"another key").verify_at::<Lobby>(&secret, before),
+            Err(SecretError::InvalidSignature)
+        ));
+        assert!(matches!(
+            signer.verify_at::<u64>(&secret, before),
+            Err(SecretError::Deserialize(_))
+        ));
+    }
+
+    #[test]
+    fn reject_tampered_secret() {
+        let signer = SecretSigner::new(b"0123456789abcdef0123456789abcdef");
+        let expires_at = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let secret = signer.sign_until(&lobby(), expires_at).unwrap();
+        let (_, tag) = secret.split_once('.').unwrap();
+        let forged = signer.sign_until(&"another lobby", expires_at).unwrap();
+        let (forged_body, _) = forged.split_once('.').unwrap();
+        let before = expires_at - TimeDelta::seconds(1);
+        assert!(matches!(
+            signer.verify_at::<Lobby>(&format!("{forged_body}.{tag}"), before),
+            Err(SecretError::InvalidSignature)
+        ));
+        assert!(matches!(
+            signer.verify_at::<Lobby>("not a secret", before),
+            Err(SecretError::Malformed)
+        ));
+        assert!(matches!(
+            signer.sign_until(&"x".repeat(100), expires_at),
+            Err(SecretError::TooLong(_))
+        ));
+    }
+}
@@ -0,0 +1,19 @@
+//! Background tasks tied to the lifetime of the value that started them.
+use tokio::task::JoinHandle;
+
+/// Aborts the task it holds when it is dropped
+#[derive(Debug)]
+pub(crate) struct AbortOnDrop(JoinHandle<()>);
+
+impl AbortOnDrop {
+    /// Spawns `task` on the current tokio runtime
+    pub(crate) fn spawn(task: impl Future<Output = ()> + Send + 'static) -> Self {
+        Self(tokio::spawn(task))
+    }
+}
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
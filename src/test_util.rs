@@ -0,0 +1,74 @@
+//! Mock Discord IPC server for testing the client end to end.
+use bytes::Bytes;
+use futures::{SinkExt, StreamExt};
+use serde_json::{Value, json};
+use tokio::net::UnixStream;
+use tokio_util::codec::Framed;
+
+use crate::{
+    client::SdkClient,
+    codec::{Frame, FrameCodec},
+    config::Config,
+    payload::common::opcode::Opcode,
+};
+
+/// Server side of the IPC socket of a client created by [mock_client]
+#[derive(Debug)]
+pub(crate) struct MockDiscord {
+    framed: Framed<UnixStream, FrameCodec>,
+}
+
+/// Connects a client to a [MockDiscord] that completed the handshake
+pub(crate) async fn mock_client() -> (SdkClient, MockDiscord) {
+    let (client, server) = UnixStream::pair().unwrap();
+    let config = Config::builder()
+        .serializer_channel_buffer_size(16)
+        .deserializer_channel_buffer_size(16)
+        .serializer_num_threads(1)
+        .deserializer_num_threads(1)
+        .request_timeout(5)
+        .build();
+    let client = tokio::spawn(SdkClient::with_stream(config, "1234", client));
+    let mut discord = MockDiscord {
+        framed: Framed::new(server, FrameCodec),
+    };
+    let handshake = discord.framed.next().await.unwrap().unwrap();
+    assert_eq!(handshake.opcode, Opcode::Handshake);
+    discord
+        .send(
+            Opcode::Frame,
+            json!({"cmd": "DISPATCH", "evt": "READY", "data": {"v": 1}}),
+        )
+        .await;
+    (client.await.unwrap().unwrap(), discord)
+}
+
+impl MockDiscord {
+    /// Sends a frame to the client
+    pub(crate) async fn send(&mut self, opcode: Opcode, payload: Value) {
+        let payload = Bytes::from(serde_json::to_vec(&payload).unwrap());
+        self.framed
+            .send(Frame {
+                opcode,
+                len: payload.len() as u32,
+                payload,
+            })
+            .await
+            .unwrap();
+    }
+
+    /// Waits for the next request of the client
+    pub(crate) async fn recv(&mut self) -> Value {
+        let frame = self.framed.next().await.unwrap().unwrap();
+        serde_json::from_slice(&frame.payload).unwrap()
+    }
+
+    /// Responds to `request` with `data`
+    pub(crate) async fn respond(&mut self, request: &Value, data: Value) {
+        self.send(
+            Opcode::Frame,
+            json!({"cmd": request["cmd"], "nonce": request["nonce"], "data": data}),
+        )
+        .await;
+    }
+}